@@ -8,6 +8,7 @@ use crate::nodes::spatial;
 use lazy_static::lazy_static;
 use libstardustxr::messenger::Messenger;
 use mio::net::UnixStream;
+use nanoid::nanoid;
 use once_cell::sync::OnceCell;
 use std::sync::Arc;
 
@@ -16,21 +17,26 @@ lazy_static! {
 }
 
 pub struct Client {
+	pub uid: String,
 	pub messenger: Option<Messenger>,
 	pub scenegraph: Scenegraph,
 	pub root: OnceCell<Arc<Root>>,
 }
 impl Client {
 	pub fn new_local() -> Arc<Self> {
-		Arc::new(Client {
+		let client = Arc::new(Client {
+			uid: nanoid!(),
 			messenger: None,
 			scenegraph: Default::default(),
 			root: OnceCell::new(),
-		})
+		});
+		let _ = client.scenegraph.client.set(Arc::downgrade(&client));
+		client
 	}
 	pub fn from_connection(connection: UnixStream) -> Arc<Self> {
 		println!("New client connected");
 		let client = Arc::new(Client {
+			uid: nanoid!(),
 			messenger: Some(Messenger::new(connection)),
 			scenegraph: Default::default(),
 			root: OnceCell::new(),
@@ -51,9 +57,17 @@ impl Client {
 			Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
 		}
 	}
+	/// Drops the client's fields (and so takes them out of the field registry) right away,
+	/// even while something else still holds on to the client.
+	pub fn disconnect(&self) {
+		self.scenegraph
+			.remove_nodes_under(&format!("{}/", field::FIELD_PARENT_PATH));
+	}
 }
 impl Drop for Client {
 	fn drop(&mut self) {
 		println!("Client disconnected");
+		// drop the fields before the rest of the client instead of relying on the scenegraph's drop order
+		self.disconnect();
 	}
 }
@@ -114,6 +114,7 @@ impl EventLoop {
 						std::io::ErrorKind::WouldBlock => break,
 						std::io::ErrorKind::Interrupted => continue,
 						_ => {
+							client.disconnect();
 							clients.remove(client_id);
 							break;
 						}
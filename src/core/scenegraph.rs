@@ -34,10 +34,19 @@ impl Scenegraph {
 		Some(self.nodes.get(path)?.clone())
 	}
 
+	pub fn get_nodes(&self) -> Vec<Arc<Node>> {
+		self.nodes.iter().map(|pair| pair.value().clone()).collect()
+	}
+
 	pub fn remove_node(&self, path: &str) -> Option<Arc<Node>> {
 		let (_, node) = self.nodes.remove(path)?;
 		Some(node)
 	}
+
+	/// Removes every node whose path starts with `prefix`, like all of a client's fields.
+	pub fn remove_nodes_under(&self, prefix: &str) {
+		self.nodes.retain(|path, _| !path.starts_with(prefix));
+	}
 }
 
 impl scenegraph::Scenegraph for Scenegraph {
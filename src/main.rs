@@ -18,6 +18,7 @@ struct CliArgs {
 
 fn main() -> Result<()> {
 	let cli_args = CliArgs::parse();
+	env_logger::init();
 	ctrlc::set_handler(sk_quit).expect("Error setting Ctrl-C handler");
 
 	let mut init_settings = SKSettings::default().app_name("Stardust XR");
@@ -5,7 +5,7 @@ use super::item::{Item, ItemAcceptor, ItemUI};
 use super::spatial::Spatial;
 use crate::core::client::Client;
 use crate::core::registry::Registry;
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, ensure, Result};
 use libstardustxr::scenegraph::ScenegraphError;
 use nanoid::nanoid;
 use once_cell::sync::OnceCell;
@@ -20,6 +20,30 @@ use rustc_hash::FxHasher;
 pub type Signal = fn(&Node, Arc<Client>, &[u8]) -> Result<()>;
 pub type Method = fn(&Node, Arc<Client>, &[u8]) -> Result<Vec<u8>>;
 
+/// The arguments of a method, making sure at least the `required` ones (named for the error)
+/// are there, since flexbuffers reads missing ones as zero/empty instead of failing.
+pub(crate) fn flex_args<'a>(
+	data: &'a [u8],
+	required: &[&str],
+) -> Result<flexbuffers::VectorReader<&'a [u8]>> {
+	let flex_vec = flexbuffers::Reader::get_root(data)?.get_vector()?;
+	check_flex_args(&flex_vec, required)?;
+	Ok(flex_vec)
+}
+pub(crate) fn check_flex_args<B: flexbuffers::Buffer>(
+	flex_vec: &flexbuffers::VectorReader<B>,
+	required: &[&str],
+) -> Result<()> {
+	ensure!(
+		flex_vec.len() >= required.len(),
+		"Expected {} arguments ({}) but got {}",
+		required.len(),
+		required.join(", "),
+		flex_vec.len()
+	);
+	Ok(())
+}
+
 pub struct Node {
 	pub(super) uid: String,
 	pub(crate) client: Weak<Client>,
@@ -84,6 +108,8 @@ impl Node {
 			input_handler: OnceCell::new(),
 		};
 		node.add_local_signal("destroy", Node::destroy_flex);
+		node.add_local_method("getPath", Node::get_path_flex);
+		node.add_local_method("listMethods", Node::list_methods_flex);
 		node
 	}
 	pub fn add_to_scenegraph(self) -> Arc<Node> {
@@ -100,6 +126,45 @@ impl Node {
 		Ok(())
 	}
 
+	pub fn get_path_flex(
+		node: &Node,
+		_calling_client: Arc<Client>,
+		_data: &[u8],
+	) -> Result<Vec<u8>> {
+		Ok(flexbuffers::singleton(node.get_path()))
+	}
+
+	pub fn local_signal_names(&self) -> Vec<String> {
+		let mut names: Vec<String> = self.local_signals.iter().map(|e| e.key().clone()).collect();
+		names.sort();
+		names
+	}
+	pub fn local_method_names(&self) -> Vec<String> {
+		let mut names: Vec<String> = self.local_methods.iter().map(|e| e.key().clone()).collect();
+		names.sort();
+		names
+	}
+	pub fn list_methods_flex(
+		node: &Node,
+		_calling_client: Arc<Client>,
+		_data: &[u8],
+	) -> Result<Vec<u8>> {
+		let mut fbb = flexbuffers::Builder::default();
+		let mut vec = fbb.start_vector();
+		let mut methods = vec.start_vector();
+		for name in node.local_method_names() {
+			methods.push(name.as_str());
+		}
+		methods.end_vector();
+		let mut signals = vec.start_vector();
+		for name in node.local_signal_names() {
+			signals.push(name.as_str());
+		}
+		signals.end_vector();
+		vec.end_vector();
+		Ok(fbb.view().to_vec())
+	}
+
 	pub fn add_local_signal(&self, name: &str, signal: Signal) {
 		self.local_signals.insert(name.to_string(), signal);
 	}
@@ -147,11 +212,11 @@ impl Node {
 				.ok_or(ScenegraphError::BrokenAlias)?
 				.execute_local_method(calling_client, method, data)
 		} else {
-			let method = self
+			let method_fn = self
 				.local_methods
 				.get(method)
 				.ok_or(ScenegraphError::MethodNotFound)?;
-			method(self, calling_client, data)
+			method_fn(self, calling_client, data)
 				.map_err(|error| ScenegraphError::MethodError { error })
 		}
 	}
@@ -0,0 +1,425 @@
+use super::{flex_to_finite_vec3, get_reference_space_flex, DistanceMetric, MAX_SMOOTHED_KEYS};
+use crate::core::client::Client;
+use crate::nodes::core::{flex_args, Node};
+use anyhow::{ensure, Result};
+use dashmap::DashMap;
+use glam::{Mat4, Vec3A};
+use libstardustxr::fusion::flex::FlexBuffable;
+use parking_lot::Mutex;
+use portable_atomic::AtomicF32;
+use rustc_hash::FxHasher;
+use std::hash::BuildHasherDefault;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Bumped by every field shape or parameter setter, every spatial transform change and every
+/// field being dropped, since those change the distances of fields besides the one they touch.
+static SCENE_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+/// Opt-in memo of the last distance query, for static scenes asking the same thing every frame.
+/// It's keyed on the query point, metric and the whole transform to the field, and goes stale
+/// whenever anything in the scene changes (see `invalidate_all`), including a composite's children.
+#[derive(Default)]
+pub struct DistanceCache {
+	enabled: AtomicBool,
+	last: Mutex<Option<CachedDistance>>,
+	hits: AtomicU64,
+}
+#[derive(Clone, Copy)]
+struct CachedDistance {
+	generation: u64,
+	matrix: Mat4,
+	p: Vec3A,
+	metric: DistanceMetric,
+	distance: f32,
+}
+impl DistanceCache {
+	pub(super) fn get_or_insert_with(
+		&self,
+		matrix: Mat4,
+		p: Vec3A,
+		metric: DistanceMetric,
+		distance: impl FnOnce() -> f32,
+	) -> f32 {
+		if !self.enabled.load(Ordering::Relaxed) {
+			return distance();
+		}
+		let generation = SCENE_GENERATION.load(Ordering::Acquire);
+		if let Some(last) = *self.last.lock() {
+			if last.generation == generation
+				&& last.matrix == matrix
+				&& last.p == p
+				&& last.metric == metric
+			{
+				self.hits.fetch_add(1, Ordering::Relaxed);
+				return last.distance;
+			}
+		}
+		let distance = distance();
+		*self.last.lock() = Some(CachedDistance {
+			generation,
+			matrix,
+			p,
+			metric,
+			distance,
+		});
+		distance
+	}
+	pub fn set_enabled(&self, enabled: bool) {
+		self.enabled.store(enabled, Ordering::Relaxed);
+		self.invalidate();
+	}
+	pub fn invalidate(&self) {
+		*self.last.lock() = None;
+	}
+	/// Makes every field's cached distance stale.
+	pub fn invalidate_all() {
+		SCENE_GENERATION.fetch_add(1, Ordering::Release);
+	}
+	pub fn hits(&self) -> u64 {
+		self.hits.load(Ordering::Relaxed)
+	}
+}
+/// Exponentially smoothed distances for tracked points, keyed by a name the client picks per
+/// point, so jittery tracking doesn't make haptics buzz.
+#[derive(Default)]
+pub struct DistanceSmoothing {
+	smoothed: DashMap<String, f32, BuildHasherDefault<FxHasher>>,
+}
+impl DistanceSmoothing {
+	/// Blends `distance` into the last smoothed value for `key` by `alpha`, so 1 is no smoothing.
+	/// The first value for a key is passed through as is.
+	pub fn smooth(&self, key: &str, distance: f32, alpha: f32) -> Result<f32> {
+		ensure!(
+			self.smoothed.contains_key(key) || self.smoothed.len() < MAX_SMOOTHED_KEYS,
+			"Too many smoothed distances, clear some first (at most {})",
+			MAX_SMOOTHED_KEYS
+		);
+		let mut smoothed = self.smoothed.entry(key.to_string()).or_insert(distance);
+		*smoothed += (distance - *smoothed) * alpha;
+		Ok(*smoothed)
+	}
+	pub fn clear(&self, key: &str) {
+		self.smoothed.remove(key);
+	}
+}
+pub(super) fn field_smoothed_distance_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point", "key", "alpha"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+	let key = flex_vec.idx(2).get_str()?;
+	let alpha = flex_vec.idx(3).as_f32();
+	ensure!(
+		alpha > 0_f32 && alpha <= 1_f32,
+		"Alpha must be greater than 0 and at most 1"
+	);
+
+	let field = node.field.get().unwrap();
+	let distance = field.distance(reference_space.as_ref(), point);
+	let smoothed = field
+		.common()
+		.temporal_smoothing
+		.smooth(key, distance, alpha)?;
+	Ok(FlexBuffable::from(smoothed).build_singleton())
+}
+pub(super) fn field_clear_smoothed_distance_flex(
+	node: &Node,
+	_calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	let key = flexbuffers::Reader::get_root(data)?.get_str()?;
+	node.field
+		.get()
+		.unwrap()
+		.common()
+		.temporal_smoothing
+		.clear(key);
+	Ok(())
+}
+/// Opt-in level of detail for expensive fields, answering distance queries further than
+/// `threshold` (in the field's space) outside its bounding sphere with the distance to that
+/// sphere instead. That underestimates the distance so it's still safe to march.
+pub struct FieldLod {
+	threshold: AtomicF32,
+}
+impl Default for FieldLod {
+	fn default() -> Self {
+		FieldLod {
+			threshold: AtomicF32::new(f32::INFINITY),
+		}
+	}
+}
+impl FieldLod {
+	/// A negative `threshold` turns it off.
+	pub fn set_threshold(&self, threshold: f32) {
+		let threshold = if threshold < 0_f32 {
+			f32::INFINITY
+		} else {
+			threshold
+		};
+		self.threshold.store(threshold, Ordering::Relaxed);
+		DistanceCache::invalidate_all();
+	}
+	pub(super) fn proxy_distance(&self, bounding_radius: f32, local_p: Vec3A) -> Option<f32> {
+		let proxy_distance = local_p.length() - bounding_radius;
+		(proxy_distance > self.threshold.load(Ordering::Relaxed)).then(|| proxy_distance)
+	}
+}
+pub(super) fn field_set_lod_threshold_flex(
+	node: &Node,
+	_calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	let threshold = flexbuffers::Reader::get_root(data)?.as_f32();
+	ensure!(!threshold.is_nan(), "LOD threshold is NaN");
+	node.field
+		.get()
+		.unwrap()
+		.common()
+		.lod
+		.set_threshold(threshold);
+	Ok(())
+}
+pub(super) fn field_set_cache_enabled_flex(
+	node: &Node,
+	_calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	let root = flexbuffers::Reader::get_root(data)?;
+	node.field
+		.get()
+		.unwrap()
+		.common()
+		.cache
+		.set_enabled(root.as_bool());
+	Ok(())
+}
+pub(super) fn field_get_cache_hits_flex(
+	node: &Node,
+	_calling_client: Arc<Client>,
+	_data: &[u8],
+) -> Result<Vec<u8>> {
+	let hits = node.field.get().unwrap().common().cache.hits();
+	Ok(flexbuffers::singleton(hits))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::nodes::field::tests::*;
+	use crate::nodes::field::{next_random, DEFAULT_SAMPLE_SEED};
+	use glam::{vec3, Vec3};
+	use libstardustxr::flex::flexbuffer_from_vector_arguments;
+	use libstardustxr::push_to_vec;
+
+	#[test]
+	fn smoothing_reduces_variance_and_tracks_mean() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "smoothed", Vec3::ZERO, 0.5_f32);
+		let mut state = DEFAULT_SAMPLE_SEED;
+		let (mut raw, mut smoothed) = (Vec::new(), Vec::new());
+		for _ in 0..200 {
+			let noise = (next_random(&mut state) - 0.5_f32) * 0.1_f32;
+			let data = flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				push_to_vec!(vec, mint::Vector3::from(vec3(0_f32, 1_f32 + noise, 0_f32)));
+				vec.push("hand");
+				vec.push(0.1_f32);
+			});
+			let result = method(&client, sphere.get_path(), "smoothed_distance", &data).unwrap();
+			raw.push(0.5_f32 + noise);
+			smoothed.push(read_f32(&result));
+		}
+		// skip the warm up, the first value is passed straight through
+		let stats = |values: &[f32]| {
+			let mean = values.iter().sum::<f32>() / values.len() as f32;
+			let variance =
+				values.iter().map(|v| (v - mean) * (v - mean)).sum::<f32>() / values.len() as f32;
+			(mean, variance)
+		};
+		let (raw_mean, raw_variance) = stats(&raw[50..]);
+		let (smoothed_mean, smoothed_variance) = stats(&smoothed[50..]);
+		assert!(smoothed_variance < raw_variance * 0.25_f32);
+		assert_near(smoothed_mean, raw_mean, 0.01_f32);
+	}
+
+	#[test]
+	fn smoothing_keys_are_capped() {
+		let smoothing = DistanceSmoothing::default();
+		for i in 0..MAX_SMOOTHED_KEYS {
+			smoothing.smooth(&i.to_string(), 1_f32, 0.5_f32).unwrap();
+		}
+		assert!(smoothing.smooth("one too many", 1_f32, 0.5_f32).is_err());
+		// existing keys keep working, and clearing one makes room again
+		assert!(smoothing.smooth("0", 1_f32, 0.5_f32).is_ok());
+		smoothing.clear("0");
+		assert!(smoothing.smooth("one too many", 1_f32, 0.5_f32).is_ok());
+	}
+
+	#[test]
+	fn cache_hits_and_invalidates() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "cached", Vec3::ZERO, 0.5_f32);
+		let query = || distance_at(&client, &sphere, Vec3::X);
+		query();
+		query();
+		assert_eq!(cache_hits(&client, &sphere), 0);
+
+		signal(
+			&client,
+			sphere.get_path(),
+			"setCacheEnabled",
+			&flexbuffers::singleton(true),
+		);
+		assert!(repeat_hits_cache(&client, &sphere, || {
+			assert_near(query(), 0.5_f32, 0.0001_f32);
+		}));
+		set_radius(&client, &sphere, 0.75_f32);
+		assert_near(query(), 0.25_f32, 0.0001_f32);
+	}
+
+	#[test]
+	fn cache_covers_every_distance_query() {
+		let client = test_client();
+		let box_node = create_box(&client, "cached", Vec3::ZERO, Vec3::ONE);
+		signal(
+			&client,
+			box_node.get_path(),
+			"setCacheEnabled",
+			&flexbuffers::singleton(true),
+		);
+		let p = vec3(1_f32, 1_f32, 0_f32);
+		let metric_args = |metric: &str| query_args(p, |options| options.push("metric", metric));
+		let path = box_node.get_path();
+		let chebyshev =
+			|| read_f32(&method(&client, path, "distance", &metric_args("chebyshev")).unwrap());
+		assert!(repeat_hits_cache(&client, &box_node, || {
+			assert_near(chebyshev(), 0.5_f32, 0.0001_f32);
+		}));
+		// the metric is part of the key, so these don't get each other's answers
+		assert_near(
+			distance_at(&client, &box_node, p),
+			0.5_f32.sqrt(),
+			0.0001_f32,
+		);
+		assert_near(chebyshev(), 0.5_f32, 0.0001_f32);
+
+		assert!(repeat_hits_cache(&client, &box_node, || {
+			method(&client, path, "proximity", &point_args(p)).unwrap();
+		}));
+		// a box squashed to a point asks `distances` about the same corner 8 times
+		let corners_args = flexbuffer_from_vector_arguments(|vec| {
+			vec.push("/");
+			push_to_vec!(vec, mint::Vector3::from(p), mint::Vector3::from(p));
+		});
+		assert!(repeat_hits_cache(&client, &box_node, || {
+			method(&client, path, "box_corners_distance", &corners_args).unwrap();
+		}));
+	}
+
+	#[test]
+	fn composite_cache_sees_child_changes() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "child", Vec3::ZERO, 0.5_f32);
+		let union = create_field(&client, "createUnionField", "union", Vec3::ZERO, |vec| {
+			let mut children = vec.start_vector();
+			children.push(sphere.get_path());
+			children.end_vector();
+			vec.push(0_f32);
+		});
+		signal(
+			&client,
+			union.get_path(),
+			"setCacheEnabled",
+			&flexbuffers::singleton(true),
+		);
+		assert_near(distance_at(&client, &union, Vec3::X), 0.5_f32, 0.0001_f32);
+		set_radius(&client, &sphere, 0.75_f32);
+		assert_near(distance_at(&client, &union, Vec3::X), 0.25_f32, 0.0001_f32);
+	}
+
+	#[test]
+	fn cache_only_goes_stale_when_the_scene_changes() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "moved", Vec3::ZERO, 0.5_f32);
+		let path = sphere.get_path();
+		signal(
+			&client,
+			path,
+			"setCacheEnabled",
+			&flexbuffers::singleton(true),
+		);
+		let query = || distance_at(&client, &sphere, Vec3::X);
+		// signals that don't touch any shape or transform leave the cache alone
+		assert!(repeat_hits_cache(&client, &sphere, || {
+			signal(
+				&client,
+				path,
+				"setStatsEnabled",
+				&flexbuffers::singleton(false),
+			);
+			assert_near(query(), 0.5_f32, 0.0001_f32);
+		}));
+
+		signal(
+			&client,
+			path,
+			"setTransform",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("");
+				push_to_vec!(vec, mint::Vector3::from(vec3(0.25_f32, 0_f32, 0_f32)));
+			}),
+		);
+		assert_near(query(), 0.25_f32, 0.0001_f32);
+	}
+
+	#[test]
+	fn lod_answers_far_queries_with_the_bounding_sphere() {
+		let client = test_client();
+		let box_node = create_box(&client, "detailed", Vec3::ZERO, Vec3::ONE);
+		let path = box_node.get_path();
+		signal(
+			&client,
+			path,
+			"setLodThreshold",
+			&flexbuffers::singleton(1_f32),
+		);
+		let bounding_radius = 3_f32.sqrt() * 0.5_f32;
+
+		let far = Vec3::X * 10_f32;
+		assert_near(
+			distance_at(&client, &box_node, far),
+			10_f32 - bounding_radius,
+			0.0001_f32,
+		);
+		let corners_args = flexbuffer_from_vector_arguments(|vec| {
+			vec.push("/");
+			push_to_vec!(vec, mint::Vector3::from(far), mint::Vector3::from(far));
+		});
+		let corners_distance =
+			read_f32(&method(&client, path, "box_corners_distance", &corners_args).unwrap());
+		assert_near(corners_distance, 10_f32 - bounding_radius, 0.0001_f32);
+		// chebyshev distances can be shorter than the bounding sphere's, so they stay exact
+		let chebyshev_args = query_args(far, |options| options.push("metric", "chebyshev"));
+		let chebyshev = read_f32(&method(&client, path, "distance", &chebyshev_args).unwrap());
+		assert_near(chebyshev, 9.5_f32, 0.0001_f32);
+
+		// near queries are exact, as are far ones once it's turned off again
+		assert_near(
+			distance_at(&client, &box_node, Vec3::X),
+			0.5_f32,
+			0.0001_f32,
+		);
+		signal(
+			&client,
+			path,
+			"setLodThreshold",
+			&flexbuffers::singleton(-1_f32),
+		);
+		assert_near(distance_at(&client, &box_node, far), 9.5_f32, 0.0001_f32);
+	}
+}
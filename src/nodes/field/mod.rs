@@ -0,0 +1,5351 @@
+use super::core::{check_flex_args, flex_args, Method, Node, Signal};
+use super::spatial::{get_spatial_parent_flex, Spatial};
+use crate::core::client::Client;
+use crate::core::registry::Registry;
+use anyhow::{anyhow, ensure, Result};
+use glam::{
+	swizzles::*, uvec2, uvec3, vec2, vec3, vec3a, Mat4, Quat, UVec2, UVec3, Vec2, Vec3, Vec3A,
+};
+use half::f16;
+use lazy_static::lazy_static;
+use libstardustxr::flex::flexbuffer_from_vector_arguments;
+use libstardustxr::fusion::flex::FlexBuffable;
+use libstardustxr::{flex_to_quat, flex_to_vec3, push_to_vec};
+use parking_lot::Mutex;
+use std::cell::Cell;
+use std::ops::{Deref, Range};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
+
+/// Registers a field query so it runs through `field_query`. Methods are plain function
+/// pointers, so the wrapper can't be a closure capturing the query it wraps.
+macro_rules! add_field_method {
+	($node:expr, $name:literal, $query:expr) => {
+		$node.add_local_method($name, |node, calling_client, data| {
+			$crate::nodes::field::field_query(node, calling_client, data, $name, $query)
+		})
+	};
+}
+/// Registers a field signal so it runs through `field_signal`, like `add_field_method`.
+macro_rules! add_field_signal {
+	($node:expr, $name:literal, $signal:expr) => {
+		$node.add_local_signal($name, |node, calling_client, data| {
+			$crate::nodes::field::field_signal(node, calling_client, data, $name, $signal)
+		})
+	};
+}
+
+mod cache;
+mod operators;
+mod ray;
+mod shapes;
+
+pub use self::cache::*;
+pub use self::operators::*;
+pub use self::ray::*;
+pub use self::shapes::*;
+
+/// Bits of `value` as an f16, for queries asking for half precision results to save space.
+fn half_bits(value: f32) -> u16 {
+	f16::from_f32(value).to_bits()
+}
+/// Optional settings for the `distance`, `normal` and `closest_point` queries,
+/// given as a map after their reference space and point, e.g. `{"metric": "chebyshev", "half": true}`.
+struct QueryOptions {
+	metric: DistanceMetric,
+	/// Report how many distance evaluations the query took alongside the result.
+	evaluations: bool,
+	/// Encode the result as f16 bits instead of f32.
+	half: bool,
+	/// Give the result in the field's local space instead of the reference space.
+	local: bool,
+}
+impl QueryOptions {
+	fn from_flex(options: flexbuffers::Reader<&[u8]>) -> Result<Self> {
+		if options.flexbuffer_type() == flexbuffers::FlexBufferType::Null {
+			return Ok(QueryOptions {
+				metric: DistanceMetric::Euclidean,
+				evaluations: false,
+				half: false,
+				local: false,
+			});
+		}
+		let options = options.get_map()?;
+		Ok(QueryOptions {
+			metric: match options.idx("metric").as_str() {
+				"" | "euclidean" => DistanceMetric::Euclidean,
+				"chebyshev" => DistanceMetric::Chebyshev,
+				"manhattan" => DistanceMetric::Manhattan,
+				_ => return Err(anyhow!("Invalid distance metric")),
+			},
+			evaluations: options.idx("evaluations").as_bool(),
+			half: options.idx("half").as_bool(),
+			local: options.idx("local").as_bool(),
+		})
+	}
+}
+
+fn half_vec3_flex(v: Vec3A) -> Vec<u8> {
+	flexbuffer_from_vector_arguments(|vec| {
+		vec.push(half_bits(v.x));
+		vec.push(half_bits(v.y));
+		vec.push(half_bits(v.z));
+	})
+}
+
+fn flex_to_sample_seed<B: flexbuffers::Buffer>(flex: flexbuffers::Reader<B>) -> u64 {
+	if flex.flexbuffer_type().is_null() {
+		DEFAULT_SAMPLE_SEED
+	} else {
+		flex.as_u64()
+	}
+}
+
+fn flex_to_finite_vec3<B: flexbuffers::Buffer>(
+	flex: flexbuffers::Reader<B>,
+	name: &str,
+) -> Result<Vec3A> {
+	let vec: Vec3A = flex_to_vec3!(flex)
+		.ok_or_else(|| anyhow!("{} is invalid", name))?
+		.into();
+	ensure!(vec.is_finite(), "{} has a non-finite component", name);
+	Ok(vec)
+}
+/// A length or radius, which has to be finite and can't be negative.
+fn flex_to_size<B: flexbuffers::Buffer>(flex: flexbuffers::Reader<B>, name: &str) -> Result<f32> {
+	let size = flex.as_f32();
+	ensure!(
+		size.is_finite() && size >= 0_f32,
+		"{} must be finite and not negative",
+		name
+	);
+	Ok(size)
+}
+fn flex_to_size_vec3<B: flexbuffers::Buffer>(
+	flex: flexbuffers::Reader<B>,
+	name: &str,
+) -> Result<Vec3A> {
+	let size = flex_to_finite_vec3(flex, name)?;
+	ensure!(
+		size.cmpge(Vec3A::ZERO).all(),
+		"{} has a negative component",
+		name
+	);
+	Ok(size)
+}
+/// A scale factor, which has to be finite and positive so it can be divided by.
+fn flex_to_scale<B: flexbuffers::Buffer>(flex: flexbuffers::Reader<B>) -> Result<f32> {
+	let scale = flex.as_f32();
+	ensure!(
+		scale.is_finite() && scale > 0_f32,
+		"Scale must be positive and finite"
+	);
+	Ok(scale)
+}
+
+/// Splitmix64, so sampling is deterministic for a given seed without pulling in an RNG crate.
+fn next_random(state: &mut u64) -> f32 {
+	*state = state.wrapping_add(0x9E3779B97F4A7C15);
+	let mut z = *state;
+	z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+	z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+	z ^= z >> 31;
+	(z >> 40) as f32 / (1_u64 << 24) as f32
+}
+fn random_direction(state: &mut u64) -> Vec3A {
+	let z = (next_random(state) * 2_f32) - 1_f32;
+	let angle = next_random(state) * std::f32::consts::TAU;
+	let r = (1_f32 - (z * z)).max(0_f32).sqrt();
+	vec3a(r * angle.cos(), r * angle.sin(), z)
+}
+
+thread_local! {
+	/// Distance evaluations by this thread, for reporting what a single query cost.
+	static QUERY_EVALUATIONS: Cell<u32> = Cell::new(0);
+}
+/// Runs `query` and counts how many distance evaluations it took, including any on child fields.
+fn count_evaluations<T>(query: impl FnOnce() -> T) -> (T, u32) {
+	let outer = QUERY_EVALUATIONS.with(|evaluations| evaluations.replace(0));
+	let result = query();
+	let count =
+		QUERY_EVALUATIONS.with(|evaluations| evaluations.replace(outer + evaluations.get()));
+	(result, count)
+}
+
+/// Opt-in counters of how often and for how long a field's distance gets evaluated.
+#[derive(Default)]
+pub struct FieldStats {
+	enabled: AtomicBool,
+	evaluations: AtomicU64,
+	nanoseconds: AtomicU64,
+}
+impl FieldStats {
+	fn record(&self) -> Option<FieldStatsTimer> {
+		QUERY_EVALUATIONS.with(|evaluations| evaluations.set(evaluations.get() + 1));
+		self.enabled
+			.load(Ordering::Relaxed)
+			.then(|| FieldStatsTimer {
+				stats: self,
+				start: Instant::now(),
+			})
+	}
+	pub fn set_enabled(&self, enabled: bool) {
+		self.enabled.store(enabled, Ordering::Relaxed);
+	}
+	/// Evaluation count and total time spent, optionally resetting both.
+	pub fn read(&self, reset: bool) -> (u64, Duration) {
+		let (evaluations, nanoseconds) = if reset {
+			(
+				self.evaluations.swap(0, Ordering::Relaxed),
+				self.nanoseconds.swap(0, Ordering::Relaxed),
+			)
+		} else {
+			(
+				self.evaluations.load(Ordering::Relaxed),
+				self.nanoseconds.load(Ordering::Relaxed),
+			)
+		};
+		(evaluations, Duration::from_nanos(nanoseconds))
+	}
+}
+struct FieldStatsTimer<'a> {
+	stats: &'a FieldStats,
+	start: Instant,
+}
+impl Drop for FieldStatsTimer<'_> {
+	fn drop(&mut self) {
+		self.stats.evaluations.fetch_add(1, Ordering::Relaxed);
+		self.stats
+			.nanoseconds
+			.fetch_add(self.start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+	}
+}
+
+const SURFACE_PROJECTION_STEPS: u32 = 4;
+const SWEPT_REFINE_STEPS: u32 = 24;
+const MAX_SURFACE_SAMPLES: usize = 4096;
+const MAX_INTERIOR_SAMPLES: usize = 65536;
+const MAX_HISTOGRAM_BUCKETS: usize = 256;
+const JACOBI_SWEEPS: u32 = 8;
+const AMBIENT_OCCLUSION_STEPS: u32 = 5;
+/// Used when a sampling query doesn't pass a seed, so repeated queries get the same samples.
+const DEFAULT_SAMPLE_SEED: u64 = 0x5354_4152_4455_5354;
+const MAX_GAP_ITERATIONS: u32 = 16;
+const SEAM_TOLERANCE: f32 = 0.001_f32;
+const CONTAINMENT_SAMPLES: usize = 256;
+const MAX_SLICE_RESOLUTION: u32 = 512;
+/// Roughly how many cells of a baked grid go in each chunk, rounded to whole layers.
+const GRID_CHUNK_CELLS: u32 = 65536;
+/// Where a camera's near plane is assumed to be when the query doesn't say, along its -Z.
+const DEFAULT_NEAR_PLANE: f32 = 0.1_f32;
+const CONTACT_NUDGE: f32 = 0.01_f32;
+const CONTACT_MERGE_DISTANCE: f32 = 0.001_f32;
+/// Floor for dimensions that an SDF divides by, so zero size fields are tiny instead of NaN.
+const MIN_FIELD_SIZE: f32 = 0.0001_f32;
+/// Smoothed distances a field keeps at once, since the keys are whatever clients send.
+const MAX_SMOOTHED_KEYS: usize = 256;
+
+fn max_scale(matrix: &Mat4) -> f32 {
+	vec3(
+		matrix.x_axis.truncate().length(),
+		matrix.y_axis.truncate().length(),
+		matrix.z_axis.truncate().length(),
+	)
+	.max_element()
+}
+
+/// Two unit vectors perpendicular to `normal` and each other,
+/// using Duff et al.'s branchless orthonormal basis.
+fn orthonormal_basis(normal: Vec3A) -> (Vec3A, Vec3A) {
+	let sign = 1_f32.copysign(normal.z);
+	let a = -1_f32 / (sign + normal.z);
+	let b = normal.x * normal.y * a;
+	let tangent = vec3a(
+		1_f32 + (sign * normal.x * normal.x * a),
+		sign * b,
+		-sign * normal.x,
+	);
+	let bitangent = vec3a(b, sign + (normal.y * normal.y * a), -normal.y);
+	(tangent, bitangent)
+}
+
+/// How distance outside a field is measured. Only boxes support anything but `Euclidean`,
+/// and ray marching always needs `Euclidean` to stay correct.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum DistanceMetric {
+	Euclidean,
+	/// Largest gap along any one axis.
+	Chebyshev,
+	/// Sum of the gaps along each axis.
+	Manhattan,
+}
+
+/// What part of a field's surface a closest point is on. Fields without distinct features are all `Surface`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FieldFeature {
+	Surface,
+	Face,
+	Edge,
+	Vertex,
+}
+impl FieldFeature {
+	pub fn name(self) -> &'static str {
+		match self {
+			FieldFeature::Surface => "surface",
+			FieldFeature::Face => "face",
+			FieldFeature::Edge => "edge",
+			FieldFeature::Vertex => "vertex",
+		}
+	}
+}
+
+/// Where a field's bounding sphere is relative to a frustum.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FrustumCull {
+	Inside,
+	Outside,
+	Intersecting,
+}
+impl FrustumCull {
+	pub fn name(self) -> &'static str {
+		match self {
+			FrustumCull::Inside => "inside",
+			FrustumCull::Outside => "outside",
+			FrustumCull::Intersecting => "intersecting",
+		}
+	}
+}
+
+/// Eigenvalues and eigenvectors of a symmetric matrix, largest eigenvalue first, by Jacobi rotations.
+fn symmetric_eigen(matrix: [[f32; 3]; 3]) -> [(f32, Vec3A); 3] {
+	let mut a = matrix;
+	let mut v = [
+		[1_f32, 0_f32, 0_f32],
+		[0_f32, 1_f32, 0_f32],
+		[0_f32, 0_f32, 1_f32],
+	];
+	for _ in 0..JACOBI_SWEEPS {
+		for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+			let apq = a[p][q];
+			if apq.abs() <= f32::EPSILON {
+				continue;
+			}
+			let theta = (a[q][q] - a[p][p]) / (2_f32 * apq);
+			let t = theta.signum() / (theta.abs() + ((theta * theta) + 1_f32).sqrt());
+			let c = 1_f32 / ((t * t) + 1_f32).sqrt();
+			let s = t * c;
+			a[p][p] -= t * apq;
+			a[q][q] += t * apq;
+			a[p][q] = 0_f32;
+			a[q][p] = 0_f32;
+			let r = 3 - p - q;
+			let (arp, arq) = (a[r][p], a[r][q]);
+			a[r][p] = (c * arp) - (s * arq);
+			a[p][r] = a[r][p];
+			a[r][q] = (s * arp) + (c * arq);
+			a[q][r] = a[r][q];
+			for row in v.iter_mut() {
+				let (vrp, vrq) = (row[p], row[q]);
+				row[p] = (c * vrp) - (s * vrq);
+				row[q] = (s * vrp) + (c * vrq);
+			}
+		}
+	}
+	let mut eigen = [0, 1, 2].map(|k| (a[k][k], vec3a(v[0][k], v[1][k], v[2][k])));
+	eigen.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+	eigen
+}
+
+pub trait FieldTrait {
+	fn local_distance(&self, p: Vec3A) -> f32;
+	fn local_distance_metric(&self, p: Vec3A, _metric: DistanceMetric) -> f32 {
+		self.local_distance(p)
+	}
+	/// Surface normal at `p` from forward differences of size `r`, pointing out of the field.
+	fn local_normal(&self, p: Vec3A, r: f32) -> Vec3A {
+		let d = self.local_distance(p);
+
+		let n = vec3a(
+			self.local_distance(p + (Vec3A::X * r)),
+			self.local_distance(p + (Vec3A::Y * r)),
+			self.local_distance(p + (Vec3A::Z * r)),
+		) - vec3a(d, d, d);
+
+		n.normalize()
+	}
+	fn local_closest_point(&self, p: Vec3A, r: f32) -> Vec3A {
+		p - (self.local_normal(p, r) * self.local_distance(p))
+	}
+	/// The closest point and which feature of the surface it's on.
+	fn local_closest_feature(&self, p: Vec3A, r: f32) -> (FieldFeature, Vec3A) {
+		(FieldFeature::Surface, self.local_closest_point(p, r))
+	}
+	/// Gradient of the distance at `p` from central differences of size `r`, pointing away from the surface.
+	fn local_gradient(&self, p: Vec3A, r: f32) -> Vec3A {
+		vec3a(
+			self.local_distance(p + (Vec3A::X * r)) - self.local_distance(p - (Vec3A::X * r)),
+			self.local_distance(p + (Vec3A::Y * r)) - self.local_distance(p - (Vec3A::Y * r)),
+			self.local_distance(p + (Vec3A::Z * r)) - self.local_distance(p - (Vec3A::Z * r)),
+		) / (2_f32 * r)
+	}
+	/// How enclosed `p` is by the field, from 0 (open) to 1 (fully occluded), by checking how much
+	/// closer the field is than expected at `AMBIENT_OCCLUSION_STEPS` points `step` apart along the gradient.
+	fn local_ambient_occlusion(&self, p: Vec3A, step: f32) -> f32 {
+		let direction = self.local_gradient(p, step * 0.1_f32).normalize_or_zero();
+		let mut occlusion = 0_f32;
+		let mut weight = 1_f32;
+		for i in 1..=AMBIENT_OCCLUSION_STEPS {
+			let expected = step * i as f32;
+			let distance = self.local_distance(p + (direction * expected));
+			occlusion += ((expected - distance) / expected).max(0_f32) * weight;
+			weight *= 0.5_f32;
+		}
+		occlusion.clamp(0_f32, 1_f32)
+	}
+	/// Approximate mean curvature of the isosurface through `p`, taken as half the
+	/// divergence of the normal (the SDF's laplacian) using central differences of size `r`.
+	fn local_curvature(&self, p: Vec3A, r: f32) -> f32 {
+		let d = self.local_distance(p);
+		let laplacian = [Vec3A::X, Vec3A::Y, Vec3A::Z]
+			.iter()
+			.map(|axis| {
+				self.local_distance(p + (*axis * r)) + self.local_distance(p - (*axis * r))
+					- (2_f32 * d)
+			})
+			.sum::<f32>()
+			/ (r * r);
+		laplacian * 0.5_f32
+	}
+	/// Several distinct surface points near `p`, from the closest points of slightly nudged copies
+	/// of it, so contacts on edges and corners see every face involved.
+	fn local_contact_points(&self, p: Vec3A, r: f32) -> Vec<Vec3A> {
+		let nudge = (self.local_distance(p).abs() * 0.5_f32).max(CONTACT_NUDGE);
+		let mut contact_points: Vec<Vec3A> = Vec::new();
+		let nudges = [
+			Vec3A::ZERO,
+			Vec3A::X,
+			-Vec3A::X,
+			Vec3A::Y,
+			-Vec3A::Y,
+			Vec3A::Z,
+			-Vec3A::Z,
+		];
+		for direction in nudges {
+			let contact_point = self.local_closest_point(p + (direction * nudge), r);
+			if contact_points
+				.iter()
+				.all(|existing| existing.distance(contact_point) > CONTACT_MERGE_DISTANCE)
+			{
+				contact_points.push(contact_point);
+			}
+		}
+		contact_points
+	}
+	/// Smallest distance to the surface anywhere along the segment from `from` to `to`.
+	fn local_distance_swept(&self, from: Vec3A, to: Vec3A) -> f32 {
+		let length = from.distance(to);
+		let direction = (to - from).normalize_or_zero();
+		let distance_at = |travelled: f32| self.local_distance(from + (direction * travelled));
+		let mut distance = distance_at(0_f32);
+		let mut min_distance = distance;
+		// the samples either side of the closest one, which the closest approach lies between
+		let mut bracket = (0_f32, length);
+		let mut travelled = 0_f32;
+		let mut steps = 0;
+		while travelled < length && min_distance > 0_f32 && steps < MAX_RAY_STEPS {
+			// half steps so the closest approach between samples isn't overshot by much
+			let previous = travelled;
+			travelled = (travelled + (distance * 0.5_f32).max(MIN_RAY_MARCH)).min(length);
+			distance = distance_at(travelled);
+			if distance < min_distance {
+				min_distance = distance;
+				bracket = (previous, length);
+			} else if bracket.1 == length {
+				bracket.1 = travelled;
+			}
+			steps += 1;
+		}
+		if min_distance > 0_f32 {
+			// the distance between samples can still dip below them, so close in on the dip
+			let (mut low, mut high) = bracket;
+			for _ in 0..SWEPT_REFINE_STEPS {
+				let third = (high - low) / 3_f32;
+				if distance_at(low + third) < distance_at(high - third) {
+					high -= third;
+				} else {
+					low += third;
+				}
+			}
+			min_distance = min_distance.min(distance_at((low + high) * 0.5_f32));
+		}
+		min_distance
+	}
+	/// Normal, tangent and bitangent at `p`, with the tangents built from the normal.
+	fn local_tangent_frame(&self, p: Vec3A, r: f32) -> (Vec3A, Vec3A, Vec3A) {
+		let normal = self.local_normal(p, r);
+		let (tangent, bitangent) = orthonormal_basis(normal);
+		(normal, tangent, bitangent)
+	}
+	/// Roughly evenly spread points on the surface, found by projecting random directions
+	/// from the field's origin onto the surface.
+	fn local_surface_samples(&self, count: usize, seed: u64) -> Vec<Vec3A> {
+		let mut state = seed;
+		let start_radius = self.local_distance(Vec3A::ZERO).abs().max(MIN_RAY_MARCH);
+		(0..count)
+			.map(|_| {
+				let mut p = random_direction(&mut state) * start_radius;
+				for _ in 0..SURFACE_PROJECTION_STEPS {
+					p = self.local_closest_point(p, 0.001_f32);
+				}
+				p
+			})
+			.collect()
+	}
+
+	/// Centroid and principal axes (most spread out first) of the field's interior, estimated
+	/// from `count` random points in its bounding sphere. `None` if none of them were inside.
+	fn local_principal_axes(&self, count: usize, seed: u64) -> Option<(Vec3A, [Vec3A; 3])> {
+		let bounding_radius = self.local_bounding_radius();
+		if !bounding_radius.is_finite() {
+			return None;
+		}
+		let mut state = seed;
+		let interior: Vec<Vec3A> = (0..count)
+			.map(|_| {
+				random_direction(&mut state) * (bounding_radius * next_random(&mut state).cbrt())
+			})
+			.filter(|p| self.local_distance(*p) < 0_f32)
+			.collect();
+		if interior.is_empty() {
+			return None;
+		}
+		let centroid = interior.iter().fold(Vec3A::ZERO, |sum, p| sum + *p) / interior.len() as f32;
+		let mut covariance = [[0_f32; 3]; 3];
+		for p in &interior {
+			let offset = *p - centroid;
+			for (row, covariance_row) in covariance.iter_mut().enumerate() {
+				for (column, value) in covariance_row.iter_mut().enumerate() {
+					*value += offset[row] * offset[column];
+				}
+			}
+		}
+		let [(_, major), (_, middle), (_, minor)] = symmetric_eigen(covariance);
+		Some((centroid, [major, middle, minor]))
+	}
+
+	/// How many of `count` random points in the bounding box fall in each of `buckets` equal
+	/// ranges of distance from minus to plus the bounding radius, with anything beyond that in
+	/// the end buckets. `None` if the field is unbounded.
+	fn local_distance_histogram(
+		&self,
+		buckets: usize,
+		count: usize,
+		seed: u64,
+	) -> Option<Vec<u32>> {
+		let bounding_radius = self.local_bounding_radius();
+		if !bounding_radius.is_finite() || bounding_radius <= 0_f32 || buckets == 0 {
+			return None;
+		}
+		let mut state = seed;
+		let mut histogram = vec![0_u32; buckets];
+		for _ in 0..count {
+			let p = vec3a(
+				next_random(&mut state),
+				next_random(&mut state),
+				next_random(&mut state),
+			);
+			let distance = self.local_distance(((p * 2_f32) - Vec3A::ONE) * bounding_radius);
+			let bucket = ((distance / bounding_radius) + 1_f32) * 0.5_f32 * buckets as f32;
+			histogram[(bucket.max(0_f32) as usize).min(buckets - 1)] += 1;
+		}
+		Some(histogram)
+	}
+
+	fn local_point(&self, reference_space: &Spatial, p: Vec3A) -> Vec3A {
+		Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()))
+			.transform_point3a(p)
+	}
+	fn distance(&self, reference_space: &Spatial, p: Vec3A) -> f32 {
+		self.distance_from_matrix(
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref())),
+			p,
+		)
+	}
+	/// Distance in a space given by its matrix into this field's space (like `getMatrix` returns)
+	/// instead of by a spatial.
+	fn distance_from_matrix(&self, reference_to_local_space: Mat4, p: Vec3A) -> f32 {
+		self.distance_metric_from_matrix(reference_to_local_space, p, DistanceMetric::Euclidean)
+	}
+	/// Where every distance query in another space ends up, so they all get the cache and LOD.
+	fn distance_metric_from_matrix(
+		&self,
+		reference_to_local_space: Mat4,
+		p: Vec3A,
+		metric: DistanceMetric,
+	) -> f32 {
+		let common = self.common();
+		common
+			.cache
+			.get_or_insert_with(reference_to_local_space, p, metric, || {
+				let local_p = reference_to_local_space.transform_point3a(p);
+				// the bounding sphere only underestimates euclidean distances
+				let proxy_distance = match metric {
+					DistanceMetric::Euclidean => common
+						.lod
+						.proxy_distance(self.local_bounding_radius(), local_p),
+					_ => None,
+				};
+				let local_distance =
+					proxy_distance.unwrap_or_else(|| self.local_distance_metric(local_p, metric));
+				// back into the reference space's units, conservatively if the scale isn't uniform
+				local_distance / max_scale(&reference_to_local_space)
+			})
+	}
+	/// Distance to each of `points`, only resolving the transform between the spaces once.
+	fn distances(&self, reference_space: &Spatial, points: &[Vec3A]) -> Vec<f32> {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		points
+			.iter()
+			.map(|p| self.distance_from_matrix(reference_to_local_space, *p))
+			.collect()
+	}
+	/// Smallest distance to any corner of the box from `min` to `max` in the reference space.
+	/// Cheap and conservative, it misses the field poking into a face without reaching a corner.
+	fn box_corners_distance(&self, reference_space: &Spatial, min: Vec3A, max: Vec3A) -> f32 {
+		let corners: Vec<Vec3A> = (0..8)
+			.map(|corner| {
+				vec3a(
+					if corner & 1 == 0 { min.x } else { max.x },
+					if corner & 2 == 0 { min.y } else { max.y },
+					if corner & 4 == 0 { min.z } else { max.z },
+				)
+			})
+			.collect();
+		self.distances(reference_space, &corners)
+			.into_iter()
+			.fold(f32::MAX, f32::min)
+	}
+	/// Where the path through `points` enters and exits the field, as the index of the segment
+	/// plus how far along it the crossing is (so 1.5 is halfway from the second to the third point),
+	/// and whether it's entering. Those fractions are the same in any space, so unlike distances
+	/// they don't need converting back from the field's units.
+	fn path_crossings(&self, reference_space: &Spatial, points: &[Vec3A]) -> Vec<(f32, bool)> {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let points: Vec<Vec3A> = points
+			.iter()
+			.map(|p| reference_to_local_space.transform_point3a(*p))
+			.collect();
+		let mut crossings = Vec::new();
+		let mut inside = match points.first() {
+			Some(first) => self.local_distance(*first) < 0_f32,
+			None => return crossings,
+		};
+		for (index, segment) in points.windows(2).enumerate() {
+			let segment_length = segment[0].distance(segment[1]);
+			if segment_length == 0_f32 {
+				continue;
+			}
+			let direction = (segment[1] - segment[0]) / segment_length;
+			let mut length = 0_f32;
+			while length <= segment_length && crossings.len() < MAX_RAY_CROSSINGS {
+				let distance = self.local_distance(segment[0] + (direction * length));
+				if (distance < 0_f32) != inside {
+					inside = !inside;
+					crossings.push((index as f32 + (length / segment_length), inside));
+				}
+				length += distance.abs().max(MIN_RAY_MARCH);
+			}
+		}
+		crossings
+	}
+	/// Distance from the origin of each of `spaces`, only resolving this field's transform once.
+	fn distances_from_origins(&self, spaces: &[Arc<Spatial>]) -> Vec<f32> {
+		let world_to_local_space = self.spatial_ref().global_transform().inverse();
+		spaces
+			.iter()
+			.map(|space| {
+				self.distance_from_matrix(
+					world_to_local_space * space.global_transform(),
+					Vec3A::ZERO,
+				)
+			})
+			.collect()
+	}
+	/// Distance to each point in its own space, e.g. the same spot relative to several users,
+	/// only resolving this field's side of the transforms once.
+	fn distances_in_spaces(&self, spaces: &[Arc<Spatial>], points: &[Vec3A]) -> Vec<f32> {
+		let world_to_local_space = self.spatial_ref().global_transform().inverse();
+		spaces
+			.iter()
+			.zip(points)
+			.map(|(space, p)| {
+				self.distance_from_matrix(world_to_local_space * space.global_transform(), *p)
+			})
+			.collect()
+	}
+	fn distance_metric(&self, reference_space: &Spatial, p: Vec3A, metric: DistanceMetric) -> f32 {
+		self.distance_metric_from_matrix(
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref())),
+			p,
+			metric,
+		)
+	}
+	fn distance_swept(&self, reference_space: &Spatial, from: Vec3A, to: Vec3A) -> f32 {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		self.local_distance_swept(
+			reference_to_local_space.transform_point3a(from),
+			reference_to_local_space.transform_point3a(to),
+		) / max_scale(&reference_to_local_space)
+	}
+	/// How far from `from` to `to` (0 to 1) a sphere of `radius` can move before touching the surface,
+	/// found by conservative advancement. `None` if it never touches.
+	fn sphere_sweep(
+		&self,
+		reference_space: &Spatial,
+		from: Vec3A,
+		to: Vec3A,
+		radius: f32,
+	) -> Option<f32> {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let scale = max_scale(&reference_to_local_space);
+		let length = from.distance(to);
+		let direction = (to - from).normalize_or_zero();
+		let mut travelled = 0_f32;
+		for _ in 0..MAX_RAY_STEPS {
+			let p = reference_to_local_space.transform_point3a(from + (direction * travelled));
+			let gap = (self.local_distance(p) / scale) - radius;
+			if gap <= MIN_RAY_MARCH {
+				return Some(if length > 0_f32 {
+					travelled / length
+				} else {
+					0_f32
+				});
+			}
+			if travelled >= length {
+				return None;
+			}
+			travelled = (travelled + gap).min(length);
+		}
+		None
+	}
+	/// Distance at a camera's near plane, for UI showing how close the field is to the view.
+	/// `reference_space` is the camera, looking down its -Z, and `near_point` defaults to the
+	/// middle of the near plane.
+	fn camera_distance(&self, reference_space: &Spatial, near_point: Option<Vec3A>) -> f32 {
+		self.distance(
+			reference_space,
+			near_point.unwrap_or(Vec3A::Z * -DEFAULT_NEAR_PLANE),
+		)
+	}
+	/// Closeness from 0 (a bounding radius or more away) to 1 (on or inside the surface).
+	fn proximity(&self, reference_space: &Spatial, p: Vec3A) -> f32 {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		// in the field's units to compare with its bounding radius
+		let distance = self.distance_from_matrix(reference_to_local_space, p)
+			* max_scale(&reference_to_local_space);
+		let bounding_radius = self.local_bounding_radius();
+		if !bounding_radius.is_finite() || bounding_radius <= 0_f32 {
+			return if distance <= 0_f32 { 1_f32 } else { 0_f32 };
+		}
+		1_f32 - (distance / bounding_radius).clamp(0_f32, 1_f32)
+	}
+	/// Center and radius of the local bounding sphere in the reference space,
+	/// conservatively if the scale isn't uniform.
+	fn bounding_sphere(&self, reference_space: &Spatial) -> (Vec3A, f32) {
+		let local_to_reference_space =
+			Spatial::space_to_space_matrix(Some(self.spatial_ref()), Some(reference_space));
+		(
+			local_to_reference_space.transform_point3a(Vec3A::ZERO),
+			self.local_bounding_radius() * max_scale(&local_to_reference_space),
+		)
+	}
+	/// Culls the bounding sphere against `planes`, each a normal pointing into the frustum and
+	/// an offset so points inside have `normal.dot(p) + offset >= 0`.
+	fn frustum_cull(&self, reference_space: &Spatial, planes: &[(Vec3A, f32)]) -> FrustumCull {
+		let (center, radius) = self.bounding_sphere(reference_space);
+		let mut cull = FrustumCull::Inside;
+		for (normal, offset) in planes {
+			let distance = (normal.dot(center) + offset) / normal.length();
+			if distance < -radius {
+				return FrustumCull::Outside;
+			}
+			if distance < radius {
+				cull = FrustumCull::Intersecting;
+			}
+		}
+		cull
+	}
+	fn unsigned_distance(&self, reference_space: &Spatial, p: Vec3A) -> f32 {
+		self.distance(reference_space, p).abs()
+	}
+	fn normal(&self, reference_space: &Spatial, p: Vec3A, r: f32) -> Vec3A {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let local_p = reference_to_local_space.transform_point3a(p);
+		reference_to_local_space
+			.inverse()
+			.transform_vector3a(self.local_normal(local_p, r))
+	}
+	fn closest_point(&self, reference_space: &Spatial, p: Vec3A, r: f32) -> Vec3A {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let local_p = reference_to_local_space.transform_point3a(p);
+		reference_to_local_space
+			.inverse()
+			.transform_point3a(self.local_closest_point(local_p, r))
+	}
+	/// The closest point plus how far to move towards it without risking passing the surface,
+	/// for animating something snapping onto the field.
+	fn snap_step(&self, reference_space: &Spatial, p: Vec3A) -> (Vec3A, f32) {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let scale = max_scale(&reference_to_local_space);
+		let closest_point = self.closest_point(reference_space, p, 0.001_f32);
+		// the distance never overestimates how far the surface is, so stepping it can't overshoot
+		let step = self
+			.distance_from_matrix(reference_to_local_space, p)
+			.abs()
+			.min(self.local_bounding_radius() / scale)
+			.min(p.distance(closest_point));
+		(closest_point, step)
+	}
+	fn closest_feature(
+		&self,
+		reference_space: &Spatial,
+		p: Vec3A,
+		r: f32,
+	) -> (FieldFeature, Vec3A) {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let local_p = reference_to_local_space.transform_point3a(p);
+		let (feature, closest_point) = self.local_closest_feature(local_p, r);
+		(
+			feature,
+			reference_to_local_space
+				.inverse()
+				.transform_point3a(closest_point),
+		)
+	}
+	/// See `local_curvature`, with `r` and the result in the reference space's units.
+	fn curvature(&self, reference_space: &Spatial, p: Vec3A, r: f32) -> f32 {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let local_p = reference_to_local_space.transform_point3a(p);
+		let scale = max_scale(&reference_to_local_space);
+		// one over a length, so it grows as the field shrinks
+		self.local_curvature(local_p, r * scale) * scale
+	}
+	/// Length of the distance gradient at `p`, about 1 for an exact distance field and off
+	/// from it where the field only bounds the distance (smooth unions, subtractions, warps),
+	/// so clients can tell when `closest_point` is only approximate.
+	fn gradient_magnitude(&self, reference_space: &Spatial, p: Vec3A, r: f32) -> f32 {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let local_p = reference_to_local_space.transform_point3a(p);
+		self.local_gradient(local_p, r).length()
+	}
+	/// How fast the distance changes when moving along `direction` from `p`,
+	/// so around 1 moving straight away from the surface and 0 moving along it.
+	fn directional_derivative(&self, reference_space: &Spatial, p: Vec3A, direction: Vec3A) -> f32 {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let local_p = reference_to_local_space.transform_point3a(p);
+		let local_direction =
+			reference_to_local_space.transform_vector3a(direction.normalize_or_zero());
+		self.local_gradient(local_p, 0.001_f32).dot(local_direction)
+			/ max_scale(&reference_to_local_space)
+	}
+	/// See `local_ambient_occlusion`, with `step` in the reference space.
+	fn ambient_occlusion(&self, reference_space: &Spatial, p: Vec3A, step: f32) -> f32 {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let local_p = reference_to_local_space.transform_point3a(p);
+		self.local_ambient_occlusion(local_p, step * max_scale(&reference_to_local_space))
+	}
+	/// Estimated distance between the surfaces of this and `other`, negative if they overlap,
+	/// found by alternately projecting onto each surface.
+	fn gap(&self, reference_space: &Spatial, other: &dyn FieldTrait) -> f32 {
+		let mut p =
+			Spatial::space_to_space_matrix(Some(other.spatial_ref()), Some(reference_space))
+				.transform_point3a(Vec3A::ZERO);
+		for _ in 0..MAX_GAP_ITERATIONS {
+			p = self.closest_point(reference_space, p, 0.001_f32);
+			p = other.closest_point(reference_space, p, 0.001_f32);
+		}
+		p = self.closest_point(reference_space, p, 0.001_f32);
+		other.distance(reference_space, p)
+	}
+	/// A point on both this and `other`'s surfaces near `p`, found by alternately projecting
+	/// onto each. `None` if that didn't settle onto a seam, e.g. because the surfaces don't meet.
+	fn seam_point(
+		&self,
+		reference_space: &Spatial,
+		other: &dyn FieldTrait,
+		p: Vec3A,
+	) -> Option<Vec3A> {
+		let mut p = p;
+		for _ in 0..MAX_GAP_ITERATIONS {
+			p = self.closest_point(reference_space, p, 0.001_f32);
+			p = other.closest_point(reference_space, p, 0.001_f32);
+			let distance = self
+				.distance(reference_space, p)
+				.abs()
+				.max(other.distance(reference_space, p).abs());
+			if distance < SEAM_TOLERANCE {
+				return Some(p);
+			}
+		}
+		None
+	}
+	/// The closest point on the surface that also lies on the plane through `plane_origin`
+	/// facing `plane_normal`, found by descending along the gradient projected onto the plane.
+	/// `None` if that didn't reach the surface, e.g. because the plane doesn't cross the field.
+	fn plane_closest_point(
+		&self,
+		reference_space: &Spatial,
+		p: Vec3A,
+		plane_origin: Vec3A,
+		plane_normal: Vec3A,
+	) -> Option<Vec3A> {
+		let plane_normal = plane_normal.normalize_or_zero();
+		if plane_normal == Vec3A::ZERO {
+			return None;
+		}
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let local_to_reference_space = reference_to_local_space.inverse();
+		let scale = max_scale(&reference_to_local_space);
+		// carry the plane over by two of its directions so non-uniform scales tilt it correctly
+		let (tangent, bitangent) = orthonormal_basis(plane_normal);
+		let plane_normal = reference_to_local_space
+			.transform_vector3a(tangent)
+			.cross(reference_to_local_space.transform_vector3a(bitangent))
+			.normalize_or_zero();
+		let plane_origin = reference_to_local_space.transform_point3a(plane_origin);
+		let p = reference_to_local_space.transform_point3a(p);
+		let mut p = p - (plane_normal * (p - plane_origin).dot(plane_normal));
+		for _ in 0..MAX_GAP_ITERATIONS {
+			let distance = self.local_distance(p);
+			if (distance / scale).abs() < SEAM_TOLERANCE {
+				return Some(local_to_reference_space.transform_point3a(p));
+			}
+			let gradient = self.local_gradient(p, 0.001_f32 * scale);
+			let gradient = gradient - (plane_normal * gradient.dot(plane_normal));
+			let gradient_length_squared = gradient.length_squared();
+			if gradient_length_squared < f32::EPSILON {
+				return None;
+			}
+			p -= gradient * (distance / gradient_length_squared);
+		}
+		None
+	}
+	/// Whether this field is entirely inside `other`, checked at `count` points sampled on this
+	/// surface. Conservative: every sample has to be inside `other` by at least the seam
+	/// tolerance, but anything poking out between samples is missed.
+	fn contained_in(&self, other: &dyn FieldTrait, count: usize, seed: u64) -> bool {
+		let reference_space = self.spatial_ref();
+		other.distance(reference_space, Vec3A::ZERO) < 0_f32
+			&& self
+				.surface_samples(reference_space, count, seed)
+				.into_iter()
+				.all(|p| other.distance(reference_space, p) < -SEAM_TOLERANCE)
+	}
+	fn contact_points(&self, reference_space: &Spatial, p: Vec3A, r: f32) -> Vec<Vec3A> {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let local_to_reference_space = reference_to_local_space.inverse();
+		self.local_contact_points(reference_to_local_space.transform_point3a(p), r)
+			.into_iter()
+			.map(|contact_point| local_to_reference_space.transform_point3a(contact_point))
+			.collect()
+	}
+	fn tangent_frame(&self, reference_space: &Spatial, p: Vec3A, r: f32) -> (Vec3A, Vec3A, Vec3A) {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let local_to_reference_space = reference_to_local_space.inverse();
+		let local_p = reference_to_local_space.transform_point3a(p);
+		let (normal, tangent, bitangent) = self.local_tangent_frame(local_p, r);
+		(
+			local_to_reference_space
+				.transform_vector3a(normal)
+				.normalize(),
+			local_to_reference_space
+				.transform_vector3a(tangent)
+				.normalize(),
+			local_to_reference_space
+				.transform_vector3a(bitangent)
+				.normalize(),
+		)
+	}
+	/// Distances sampled at cell centers over a rectangle on the plane through `center`
+	/// spanned by `axes`, as rows along the second axis of columns along the first.
+	fn slice(
+		&self,
+		reference_space: &Spatial,
+		center: Vec3A,
+		axes: (Vec3A, Vec3A),
+		extents: Vec2,
+		resolution: UVec2,
+	) -> Vec<Vec<f32>> {
+		let reference_to_local_space =
+			Spatial::space_to_space_matrix(Some(reference_space), Some(self.spatial_ref()));
+		let scale = max_scale(&reference_to_local_space);
+		(0..resolution.y)
+			.map(|y| {
+				let v = (((y as f32 + 0.5_f32) / resolution.y as f32) - 0.5_f32) * extents.y;
+				(0..resolution.x)
+					.map(|x| {
+						let u =
+							(((x as f32 + 0.5_f32) / resolution.x as f32) - 0.5_f32) * extents.x;
+						let p = center + (axes.0 * u) + (axes.1 * v);
+						self.local_distance(reference_to_local_space.transform_point3a(p)) / scale
+					})
+					.collect()
+			})
+			.collect()
+	}
+	/// Distances sampled at cell centers of a grid over the box from `min` to `max`, for only
+	/// the `layers` along Z, flattened with X varying fastest then Y then Z.
+	fn bake_grid_layers(
+		&self,
+		reference_space: &Spatial,
+		min: Vec3A,
+		max: Vec3A,
+		resolution: UVec3,
+		layers: Range<u32>,
+	) -> Vec<f32> {
+		let size = max - min;
+		let center = (min + max) * 0.5_f32;
+		layers
+			.flat_map(|z| {
+				let layer_z = min.z + (((z as f32 + 0.5_f32) / resolution.z as f32) * size.z);
+				self.slice(
+					reference_space,
+					vec3a(center.x, center.y, layer_z),
+					(Vec3A::X, Vec3A::Y),
+					size.xy(),
+					resolution.xy(),
+				)
+				.into_iter()
+				.flatten()
+			})
+			.collect()
+	}
+	fn principal_axes(
+		&self,
+		reference_space: &Spatial,
+		count: usize,
+		seed: u64,
+	) -> Option<(Vec3A, [Vec3A; 3])> {
+		let local_to_reference_space =
+			Spatial::space_to_space_matrix(Some(self.spatial_ref()), Some(reference_space));
+		let (centroid, axes) = self.local_principal_axes(count, seed)?;
+		Some((
+			local_to_reference_space.transform_point3a(centroid),
+			axes.map(|axis| {
+				local_to_reference_space
+					.transform_vector3a(axis)
+					.normalize()
+			}),
+		))
+	}
+	fn surface_samples(&self, reference_space: &Spatial, count: usize, seed: u64) -> Vec<Vec3A> {
+		self.local_points_to_space(reference_space, &self.local_surface_samples(count, seed))
+	}
+	/// The inverse of `local_point` for a batch of points, only resolving the transform once.
+	fn local_points_to_space(&self, reference_space: &Spatial, points: &[Vec3A]) -> Vec<Vec3A> {
+		let local_to_reference_space =
+			Spatial::space_to_space_matrix(Some(self.spatial_ref()), Some(reference_space));
+		points
+			.iter()
+			.map(|p| local_to_reference_space.transform_point3a(*p))
+			.collect()
+	}
+
+	fn add_field_methods(&self, node: &Arc<Node>) {
+		add_field_method!(node, "distance", field_distance_flex);
+		add_field_method!(
+			node,
+			"distance_from_matrix",
+			field_distance_from_matrix_flex
+		);
+		add_field_method!(node, "local_distance", field_local_distance_flex);
+		add_field_method!(
+			node,
+			"distance_in_ancestor",
+			field_distance_in_ancestor_flex
+		);
+		add_field_method!(node, "unsigned_distance", field_unsigned_distance_flex);
+		add_field_method!(
+			node,
+			"distances_from_origins",
+			field_distances_from_origins_flex
+		);
+		add_field_method!(node, "distances_in_spaces", field_distances_in_spaces_flex);
+		add_field_method!(node, "distances_blob", field_distances_blob_flex);
+		add_field_method!(
+			node,
+			"box_corners_distance",
+			field_box_corners_distance_flex
+		);
+		add_field_method!(
+			node,
+			"quantized_distances_from_origins",
+			field_quantized_distances_from_origins_flex
+		);
+		add_field_method!(node, "distance_swept", field_distance_swept_flex);
+		add_field_method!(node, "sphere_sweep", field_sphere_sweep_flex);
+		add_field_method!(node, "proximity", field_proximity_flex);
+		add_field_method!(node, "camera_distance", field_camera_distance_flex);
+		add_field_method!(node, "frustum_cull", field_frustum_cull_flex);
+		add_field_method!(node, "normal", field_normal_flex);
+		add_field_method!(node, "closest_point", field_closest_point_flex);
+		add_field_method!(node, "closest_feature", field_closest_feature_flex);
+		add_field_method!(node, "snap_step", field_snap_step_flex);
+		add_field_method!(node, "curvature", field_curvature_flex);
+		add_field_method!(node, "gradient_magnitude", field_gradient_magnitude_flex);
+		add_field_method!(
+			node,
+			"directional_derivative",
+			field_directional_derivative_flex
+		);
+		add_field_method!(node, "ambient_occlusion", field_ambient_occlusion_flex);
+		add_field_method!(node, "contact_points", field_contact_points_flex);
+		add_field_method!(node, "closest_triangle", field_closest_triangle_flex);
+		add_field_method!(node, "closest_material", field_closest_material_flex);
+		add_field_method!(node, "ray_march_all", field_ray_march_all_flex);
+		#[cfg(feature = "field_debug")]
+		add_field_method!(node, "ray_march_trace", field_ray_march_trace_flex);
+		add_field_method!(node, "ray_march_exit", field_ray_march_exit_flex);
+		add_field_method!(node, "surface_point_along", field_surface_point_along_flex);
+		add_field_method!(node, "vertical_gap", field_vertical_gap_flex);
+		add_field_method!(node, "ray_interior_length", field_ray_interior_length_flex);
+		add_field_method!(node, "path_crossings", field_path_crossings_flex);
+		add_field_method!(node, "ray_blocked", field_ray_blocked_flex);
+		add_field_method!(node, "sample_surface", field_sample_surface_flex);
+		add_field_method!(
+			node,
+			"local_points_to_space",
+			field_local_points_to_space_flex
+		);
+		add_field_method!(node, "principal_axes", field_principal_axes_flex);
+		add_field_method!(node, "natural_axis", field_natural_axis_flex);
+		add_field_method!(node, "distance_histogram", field_distance_histogram_flex);
+		add_field_method!(node, "tangent_frame", field_tangent_frame_flex);
+		add_field_method!(node, "slice", field_slice_flex);
+		add_field_method!(node, "bake_grid_chunk", field_bake_grid_chunk_flex);
+		add_field_method!(node, "gap", field_gap_flex);
+		add_field_method!(node, "seam_point", field_seam_point_flex);
+		add_field_method!(node, "plane_closest_point", field_plane_closest_point_flex);
+		add_field_method!(node, "contained_in", field_contained_in_flex);
+		add_field_signal!(node, "setScale", field_set_scale_flex);
+		add_field_signal!(node, "aimAt", field_aim_at_flex);
+		add_field_signal!(node, "setStatsEnabled", field_set_stats_enabled_flex);
+		add_field_method!(node, "getStats", field_get_stats_flex);
+		add_field_method!(node, "smoothed_distance", field_smoothed_distance_flex);
+		add_field_signal!(
+			node,
+			"clearSmoothedDistance",
+			field_clear_smoothed_distance_flex
+		);
+		add_field_signal!(node, "setLodThreshold", field_set_lod_threshold_flex);
+		add_field_signal!(node, "setCacheEnabled", field_set_cache_enabled_flex);
+		add_field_method!(node, "getCacheHits", field_get_cache_hits_flex);
+		add_field_method!(node, "getMatrix", field_get_matrix_flex);
+	}
+
+	/// Radius around the local origin that the whole field fits inside, for culling.
+	fn local_bounding_radius(&self) -> f32 {
+		f32::INFINITY
+	}
+	/// Roughly how many primitives make up this field, so ray marching can budget its steps.
+	fn complexity(&self) -> u32 {
+		1
+	}
+	fn ray_steps(&self) -> u32 {
+		self.complexity()
+			.max(1)
+			.saturating_mul(RAY_STEPS_PER_COMPLEXITY)
+	}
+
+	fn spatial_ref(&self) -> &Spatial;
+	fn common(&self) -> &FieldCommon;
+}
+
+fn get_reference_space_flex(calling_client: &Arc<Client>, path: &str) -> Result<Arc<Spatial>> {
+	Ok(calling_client
+		.scenegraph
+		.get_node(path)
+		.ok_or_else(|| anyhow!("Reference space node does not exist"))?
+		.spatial
+		.get()
+		.ok_or_else(|| anyhow!("Reference space node does not have a spatial"))?
+		.clone())
+}
+
+fn field_distance_flex(node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+	let options = QueryOptions::from_flex(flex_vec.idx(2))?;
+
+	let (distance, evaluations) = count_evaluations(|| {
+		node.field
+			.get()
+			.unwrap()
+			.distance_metric(reference_space.as_ref(), point, options.metric)
+	});
+	if !options.evaluations {
+		return Ok(if options.half {
+			flexbuffers::singleton(half_bits(distance))
+		} else {
+			FlexBuffable::from(distance).build_singleton()
+		});
+	}
+	let mut fbb = flexbuffers::Builder::default();
+	let mut map = fbb.start_map();
+	if options.half {
+		map.push("distance", half_bits(distance));
+	} else {
+		map.push("distance", distance);
+	}
+	map.push("evaluations", evaluations);
+	map.end_map();
+	Ok(fbb.view().to_vec())
+}
+fn field_distance_from_matrix_flex(
+	node: &Node,
+	_calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["matrix", "point"])?;
+	let matrix_vec = flex_vec.idx(0).get_vector()?;
+	ensure!(
+		matrix_vec.len() == 16,
+		"Matrix has {} values instead of 16",
+		matrix_vec.len()
+	);
+	let mut columns = [0_f32; 16];
+	for (value, flex) in columns.iter_mut().zip(matrix_vec.iter()) {
+		*value = flex.as_f32();
+	}
+	let matrix = Mat4::from_cols_array(&columns);
+	ensure!(matrix.is_finite(), "Matrix has a non-finite value");
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let distance = node
+		.field
+		.get()
+		.unwrap()
+		.distance_from_matrix(matrix, point);
+	Ok(FlexBuffable::from(distance).build_singleton())
+}
+/// The cheapest query, with no reference space or transform at all.
+/// The caller has to give the point in the field's own local space already.
+fn field_local_distance_flex(
+	node: &Node,
+	_calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["point"])?;
+	let point = flex_to_finite_vec3(flex_vec.idx(0), "Point")?;
+
+	let distance = node.field.get().unwrap().local_distance(point);
+	Ok(FlexBuffable::from(distance).build_singleton())
+}
+/// Distance in the space of the field's ancestor `depth` levels up, starting from 1 for its parent.
+fn field_distance_in_ancestor_flex(
+	node: &Node,
+	_calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["depth", "point"])?;
+	let depth = flex_vec.idx(0).as_u32() as usize;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let field = node.field.get().unwrap();
+	let ancestor = field
+		.spatial_ref()
+		.ancestor(depth)
+		.ok_or_else(|| anyhow!("Field has no ancestor {} levels up", depth))?;
+	let distance = field.distance(ancestor.as_ref(), point);
+	Ok(FlexBuffable::from(distance).build_singleton())
+}
+fn field_distances_from_origins_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let spaces = flexbuffers::Reader::get_root(data)?
+		.get_vector()?
+		.iter()
+		.map(|path| get_reference_space_flex(&calling_client, path.as_str()))
+		.collect::<Result<Vec<_>>>()?;
+
+	let distances = node.field.get().unwrap().distances_from_origins(&spaces);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for distance in distances {
+			vec.push(distance);
+		}
+	}))
+}
+fn field_distances_in_spaces_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference spaces", "points"])?;
+	let spaces = flex_vec
+		.idx(0)
+		.get_vector()?
+		.iter()
+		.map(|path| get_reference_space_flex(&calling_client, path.as_str()))
+		.collect::<Result<Vec<_>>>()?;
+	let points = flex_vec
+		.idx(1)
+		.get_vector()?
+		.iter()
+		.map(|point| flex_to_finite_vec3(point, "Point"))
+		.collect::<Result<Vec<Vec3A>>>()?;
+	ensure!(
+		spaces.len() == points.len(),
+		"Got {} reference spaces but {} points",
+		spaces.len(),
+		points.len()
+	);
+
+	let distances = node
+		.field
+		.get()
+		.unwrap()
+		.distances_in_spaces(&spaces, &points);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for distance in distances {
+			vec.push(distance);
+		}
+	}))
+}
+/// Distances to a point cloud packed as little endian f32 triples, returned as a blob of little endian f32s.
+fn field_distances_blob_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "points", "count"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let blob = flex_vec.idx(1).get_blob()?.0;
+	let count = flex_vec.idx(2).as_u32() as usize;
+	ensure!(
+		blob.len() == count * 12,
+		"Point blob is {} bytes but {} points need {}",
+		blob.len(),
+		count,
+		count * 12
+	);
+	let points: Vec<Vec3A> = blob
+		.chunks_exact(12)
+		.map(|point| {
+			let component =
+				|i: usize| f32::from_le_bytes([point[i], point[i + 1], point[i + 2], point[i + 3]]);
+			vec3a(component(0), component(4), component(8))
+		})
+		.collect();
+	ensure!(
+		points.iter().all(|point| point.is_finite()),
+		"Point cloud has a non-finite point"
+	);
+
+	let distances = node
+		.field
+		.get()
+		.unwrap()
+		.distances(reference_space.as_ref(), &points);
+	let distances_blob: Vec<u8> = distances
+		.into_iter()
+		.flat_map(|distance| distance.to_le_bytes())
+		.collect();
+	let mut fbb = flexbuffers::Builder::default();
+	fbb.build_singleton(flexbuffers::Blob(distances_blob.as_slice()));
+	Ok(fbb.view().to_vec())
+}
+/// Distances as multiples of `step` packed into i16s, clamped to the range in the header.
+/// A step of 0.001 gives millimeters within about 32 meters at a quarter of the size of f32s.
+fn field_quantized_distances_from_origins_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["step", "spaces"])?;
+	let step = flex_vec.idx(0).as_f32();
+	ensure!(
+		step.is_finite() && step > 0_f32,
+		"Quantization step must be positive"
+	);
+	let spaces = flex_vec
+		.idx(1)
+		.get_vector()?
+		.iter()
+		.map(|path| get_reference_space_flex(&calling_client, path.as_str()))
+		.collect::<Result<Vec<_>>>()?;
+
+	let distances = node.field.get().unwrap().distances_from_origins(&spaces);
+	let limit = i16::MAX as f32 * step;
+	let mut fbb = flexbuffers::Builder::default();
+	let mut map = fbb.start_map();
+	map.push("step", step);
+	map.push("min", -limit);
+	map.push("max", limit);
+	let mut quantized = map.start_vector("distances");
+	for distance in distances {
+		quantized.push(
+			(distance / step)
+				.round()
+				.clamp(-i16::MAX as f32, i16::MAX as f32) as i16,
+		);
+	}
+	quantized.end_vector();
+	map.end_map();
+	Ok(fbb.view().to_vec())
+}
+fn field_box_corners_distance_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "min", "max"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let min = flex_to_finite_vec3(flex_vec.idx(1), "Box min")?;
+	let max = flex_to_finite_vec3(flex_vec.idx(2), "Box max")?;
+
+	let distance =
+		node.field
+			.get()
+			.unwrap()
+			.box_corners_distance(reference_space.as_ref(), min, max);
+	Ok(FlexBuffable::from(distance).build_singleton())
+}
+fn field_unsigned_distance_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let distance = node
+		.field
+		.get()
+		.unwrap()
+		.unsigned_distance(reference_space.as_ref(), point);
+	Ok(FlexBuffable::from(distance).build_singleton())
+}
+fn field_distance_swept_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "from", "to"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let from = flex_to_finite_vec3(flex_vec.idx(1), "From point")?;
+	let to = flex_to_finite_vec3(flex_vec.idx(2), "To point")?;
+
+	let distance = node
+		.field
+		.get()
+		.unwrap()
+		.distance_swept(reference_space.as_ref(), from, to);
+	Ok(FlexBuffable::from(distance).build_singleton())
+}
+fn field_sphere_sweep_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "from", "to", "radius"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let from = flex_to_finite_vec3(flex_vec.idx(1), "From point")?;
+	let to = flex_to_finite_vec3(flex_vec.idx(2), "To point")?;
+	let radius = flex_to_size(flex_vec.idx(3), "Radius")?;
+
+	let time_of_impact =
+		node.field
+			.get()
+			.unwrap()
+			.sphere_sweep(reference_space.as_ref(), from, to, radius);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		if let Some(time_of_impact) = time_of_impact {
+			vec.push(time_of_impact);
+		}
+	}))
+}
+fn field_proximity_flex(node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let proximity = node
+		.field
+		.get()
+		.unwrap()
+		.proximity(reference_space.as_ref(), point);
+	Ok(FlexBuffable::from(proximity).build_singleton())
+}
+fn field_camera_distance_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["camera space"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let near_point = if flex_vec.idx(1).flexbuffer_type().is_null() {
+		None
+	} else {
+		Some(flex_to_finite_vec3(flex_vec.idx(1), "Near plane point")?)
+	};
+
+	let distance = node
+		.field
+		.get()
+		.unwrap()
+		.camera_distance(reference_space.as_ref(), near_point);
+	Ok(FlexBuffable::from(distance).build_singleton())
+}
+fn field_frustum_cull_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "planes"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let planes = flex_vec
+		.idx(1)
+		.get_vector()?
+		.iter()
+		.map(|plane| {
+			let plane = plane.get_vector()?;
+			check_flex_args(&plane, &["normal", "offset"])?;
+			let normal = flex_to_finite_vec3(plane.idx(0), "Plane normal")?;
+			ensure!(normal != Vec3A::ZERO, "Plane normal is zero");
+			Ok((normal, plane.idx(1).as_f32()))
+		})
+		.collect::<Result<Vec<(Vec3A, f32)>>>()?;
+	ensure!(planes.len() == 6, "Frustum needs 6 planes");
+
+	let cull = node
+		.field
+		.get()
+		.unwrap()
+		.frustum_cull(reference_space.as_ref(), &planes);
+	Ok(flexbuffers::singleton(cull.name()))
+}
+fn field_normal_flex(node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let options = QueryOptions::from_flex(flex_vec.idx(2))?;
+
+	let field = node.field.get().unwrap();
+	let (normal, evaluations) = count_evaluations(|| {
+		if options.local {
+			field.local_normal(
+				field.local_point(reference_space.as_ref(), point),
+				0.001_f32,
+			)
+		} else {
+			field.normal(reference_space.as_ref(), point, 0.001_f32)
+		}
+	});
+	if !options.evaluations {
+		return Ok(if options.half {
+			half_vec3_flex(normal)
+		} else {
+			FlexBuffable::from(mint::Vector3::from(normal)).build_singleton()
+		});
+	}
+	let mut fbb = flexbuffers::Builder::default();
+	let mut map = fbb.start_map();
+	let mut normal_vec = map.start_vector("normal");
+	for component in normal.to_array() {
+		if options.half {
+			normal_vec.push(half_bits(component));
+		} else {
+			normal_vec.push(component);
+		}
+	}
+	normal_vec.end_vector();
+	map.push("evaluations", evaluations);
+	map.end_map();
+	Ok(fbb.view().to_vec())
+}
+fn field_closest_point_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let options = QueryOptions::from_flex(flex_vec.idx(2))?;
+
+	let field = node.field.get().unwrap();
+	let closest_point = if options.local {
+		field.local_closest_point(
+			field.local_point(reference_space.as_ref(), point),
+			0.001_f32,
+		)
+	} else {
+		field.closest_point(reference_space.as_ref(), point, 0.001_f32)
+	};
+	if options.half {
+		return Ok(half_vec3_flex(closest_point));
+	}
+	Ok(FlexBuffable::from(mint::Vector3::from(closest_point)).build_singleton())
+}
+fn field_closest_feature_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let (feature, closest_point) =
+		node.field
+			.get()
+			.unwrap()
+			.closest_feature(reference_space.as_ref(), point, 0.001_f32);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		vec.push(feature.name());
+		push_to_vec!(vec, mint::Vector3::from(closest_point));
+	}))
+}
+fn field_snap_step_flex(node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let (closest_point, step) = node
+		.field
+		.get()
+		.unwrap()
+		.snap_step(reference_space.as_ref(), point);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		push_to_vec!(vec, mint::Vector3::from(closest_point), step);
+	}))
+}
+fn field_curvature_flex(node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let curvature = node
+		.field
+		.get()
+		.unwrap()
+		.curvature(reference_space.as_ref(), point, 0.01_f32);
+	Ok(FlexBuffable::from(curvature).build_singleton())
+}
+fn field_gradient_magnitude_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let magnitude =
+		node.field
+			.get()
+			.unwrap()
+			.gradient_magnitude(reference_space.as_ref(), point, 0.001_f32);
+	Ok(FlexBuffable::from(magnitude).build_singleton())
+}
+fn field_directional_derivative_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point", "direction"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+	let direction = flex_to_finite_vec3(flex_vec.idx(2), "Direction")?;
+	ensure!(direction.length_squared() > 0_f32, "Direction is zero");
+
+	let derivative = node.field.get().unwrap().directional_derivative(
+		reference_space.as_ref(),
+		point,
+		direction,
+	);
+	Ok(FlexBuffable::from(derivative).build_singleton())
+}
+fn field_ambient_occlusion_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point", "step"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+	let step = flex_vec.idx(2).as_f32();
+	ensure!(
+		step.is_finite() && step > 0_f32,
+		"Ambient occlusion step must be positive"
+	);
+
+	let occlusion =
+		node.field
+			.get()
+			.unwrap()
+			.ambient_occlusion(reference_space.as_ref(), point, step);
+	Ok(FlexBuffable::from(occlusion).build_singleton())
+}
+fn field_contact_points_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let contact_points =
+		node.field
+			.get()
+			.unwrap()
+			.contact_points(reference_space.as_ref(), point, 0.001_f32);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for contact_point in contact_points {
+			push_to_vec!(vec, mint::Vector3::from(contact_point));
+		}
+	}))
+}
+fn field_closest_triangle_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let mesh_field = match node.field.get().unwrap().as_ref() {
+		Field::Mesh(mesh_field) => mesh_field,
+		_ => return Err(anyhow!("Field is not a mesh field")),
+	};
+	let hit = mesh_field.closest_triangle(mesh_field.local_point(reference_space.as_ref(), point));
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		vec.push(hit.triangle as u32);
+		push_to_vec!(vec, mint::Vector3::from(hit.barycentric));
+	}))
+}
+/// The closest point on a mesh field and the material id of the triangle it's on.
+fn field_closest_material_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let mesh_field = match node.field.get().unwrap().as_ref() {
+		Field::Mesh(mesh_field) => mesh_field,
+		_ => return Err(anyhow!("Field is not a mesh field")),
+	};
+	let hit = mesh_field.closest_triangle(mesh_field.local_point(reference_space.as_ref(), point));
+	let closest_point = mesh_field.local_points_to_space(reference_space.as_ref(), &[hit.point])[0];
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		push_to_vec!(vec, mint::Vector3::from(closest_point));
+		vec.push(mesh_field.materials[hit.triangle]);
+	}))
+}
+fn field_path_crossings_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "points"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let points = flex_vec
+		.idx(1)
+		.get_vector()?
+		.iter()
+		.map(|point| flex_to_finite_vec3(point, "Point"))
+		.collect::<Result<Vec<Vec3A>>>()?;
+
+	let crossings = node
+		.field
+		.get()
+		.unwrap()
+		.path_crossings(reference_space.as_ref(), &points);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for (position, entering) in crossings {
+			vec.push(position);
+			vec.push(entering);
+		}
+	}))
+}
+fn field_sample_surface_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "count"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let count = flex_vec.idx(1).as_u32() as usize;
+	ensure!(
+		count <= MAX_SURFACE_SAMPLES,
+		"Too many surface samples requested (max {})",
+		MAX_SURFACE_SAMPLES
+	);
+	let seed = flex_to_sample_seed(flex_vec.idx(2));
+
+	let samples = node
+		.field
+		.get()
+		.unwrap()
+		.surface_samples(reference_space.as_ref(), count, seed);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for sample in samples {
+			push_to_vec!(vec, mint::Vector3::from(sample));
+		}
+	}))
+}
+fn field_local_points_to_space_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "points"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let points = flex_vec
+		.idx(1)
+		.get_vector()?
+		.iter()
+		.map(|point| flex_to_finite_vec3(point, "Point"))
+		.collect::<Result<Vec<Vec3A>>>()?;
+
+	let points = node
+		.field
+		.get()
+		.unwrap()
+		.local_points_to_space(reference_space.as_ref(), &points);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for point in points {
+			push_to_vec!(vec, mint::Vector3::from(point));
+		}
+	}))
+}
+fn field_principal_axes_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "count"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let count = flex_vec.idx(1).as_u32() as usize;
+	ensure!(
+		count <= MAX_INTERIOR_SAMPLES,
+		"Too many interior samples requested (max {})",
+		MAX_INTERIOR_SAMPLES
+	);
+	let seed = flex_to_sample_seed(flex_vec.idx(2));
+
+	let principal_axes =
+		node.field
+			.get()
+			.unwrap()
+			.principal_axes(reference_space.as_ref(), count, seed);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		if let Some((centroid, [major, middle, minor])) = principal_axes {
+			push_to_vec!(
+				vec,
+				mint::Vector3::from(centroid),
+				mint::Vector3::from(major),
+				mint::Vector3::from(middle),
+				mint::Vector3::from(minor)
+			);
+		}
+	}))
+}
+fn field_distance_histogram_flex(
+	node: &Node,
+	_calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["buckets", "count"])?;
+	let buckets = flex_vec.idx(0).as_u32() as usize;
+	ensure!(
+		buckets > 0 && buckets <= MAX_HISTOGRAM_BUCKETS,
+		"Histogram needs between 1 and {} buckets",
+		MAX_HISTOGRAM_BUCKETS
+	);
+	let count = flex_vec.idx(1).as_u32() as usize;
+	ensure!(
+		count <= MAX_INTERIOR_SAMPLES,
+		"Too many histogram samples requested (max {})",
+		MAX_INTERIOR_SAMPLES
+	);
+	let seed = flex_to_sample_seed(flex_vec.idx(2));
+
+	let histogram = node
+		.field
+		.get()
+		.unwrap()
+		.local_distance_histogram(buckets, count, seed);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for bucket in histogram.into_iter().flatten() {
+			vec.push(bucket);
+		}
+	}))
+}
+fn field_natural_axis_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+
+	let axis = node
+		.field
+		.get()
+		.unwrap()
+		.natural_axis(reference_space.as_ref());
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		if let Some(axis) = axis {
+			push_to_vec!(vec, mint::Vector3::from(axis));
+		}
+	}))
+}
+fn field_tangent_frame_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let (normal, tangent, bitangent) =
+		node.field
+			.get()
+			.unwrap()
+			.tangent_frame(reference_space.as_ref(), point, 0.001_f32);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		push_to_vec!(
+			vec,
+			mint::Vector3::from(normal),
+			mint::Vector3::from(tangent),
+			mint::Vector3::from(bitangent)
+		);
+	}))
+}
+fn field_slice_flex(node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(
+		data,
+		&[
+			"reference space",
+			"center",
+			"first axis",
+			"second axis",
+			"first extent",
+			"second extent",
+			"first resolution",
+			"second resolution",
+		],
+	)?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let center = flex_to_finite_vec3(flex_vec.idx(1), "Center")?;
+	let axes = (
+		flex_to_finite_vec3(flex_vec.idx(2), "First axis")?,
+		flex_to_finite_vec3(flex_vec.idx(3), "Second axis")?,
+	);
+	let extents = vec2(flex_vec.idx(4).as_f32(), flex_vec.idx(5).as_f32());
+	let resolution = uvec2(flex_vec.idx(6).as_u32(), flex_vec.idx(7).as_u32());
+	ensure!(
+		resolution.max_element() <= MAX_SLICE_RESOLUTION,
+		"Slice resolution too high (max {})",
+		MAX_SLICE_RESOLUTION
+	);
+
+	let slice = node.field.get().unwrap().slice(
+		reference_space.as_ref(),
+		center,
+		axes,
+		extents,
+		resolution,
+	);
+	let mut fbb = flexbuffers::Builder::default();
+	let mut rows = fbb.start_vector();
+	for row in slice {
+		let mut columns = rows.start_vector();
+		for distance in row {
+			columns.push(distance);
+		}
+		columns.end_vector();
+	}
+	rows.end_vector();
+	Ok(fbb.view().to_vec())
+}
+fn field_bake_grid_chunk_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(
+		data,
+		&[
+			"reference space",
+			"min",
+			"max",
+			"x resolution",
+			"y resolution",
+			"z resolution",
+			"chunk",
+		],
+	)?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let min = flex_to_finite_vec3(flex_vec.idx(1), "Min")?;
+	let max = flex_to_finite_vec3(flex_vec.idx(2), "Max")?;
+	let resolution = uvec3(
+		flex_vec.idx(3).as_u32(),
+		flex_vec.idx(4).as_u32(),
+		flex_vec.idx(5).as_u32(),
+	);
+	ensure!(
+		resolution.min_element() > 0,
+		"Grid resolution must be at least 1"
+	);
+	ensure!(
+		resolution.max_element() <= MAX_SLICE_RESOLUTION,
+		"Grid resolution too high (max {})",
+		MAX_SLICE_RESOLUTION
+	);
+	let chunk = flex_vec.idx(6).as_u32();
+	let layers_per_chunk = (GRID_CHUNK_CELLS / (resolution.x * resolution.y)).max(1);
+	let chunk_count = (resolution.z + layers_per_chunk - 1) / layers_per_chunk;
+	ensure!(
+		chunk < chunk_count,
+		"Chunk {} out of range ({} chunks)",
+		chunk,
+		chunk_count
+	);
+	let layers = chunk * layers_per_chunk..((chunk + 1) * layers_per_chunk).min(resolution.z);
+
+	let distances = node.field.get().unwrap().bake_grid_layers(
+		reference_space.as_ref(),
+		min,
+		max,
+		resolution,
+		layers.clone(),
+	);
+	// every chunk carries the header so they can be requested and uploaded in any order
+	let mut fbb = flexbuffers::Builder::default();
+	let mut vec = fbb.start_vector();
+	let mut dimensions = vec.start_vector();
+	dimensions.push(resolution.x);
+	dimensions.push(resolution.y);
+	dimensions.push(resolution.z);
+	dimensions.end_vector();
+	for corner in [min, max] {
+		let mut bound = vec.start_vector();
+		bound.push(corner.x);
+		bound.push(corner.y);
+		bound.push(corner.z);
+		bound.end_vector();
+	}
+	vec.push(chunk_count);
+	vec.push(layers.start);
+	let mut chunk_distances = vec.start_vector();
+	for distance in distances {
+		chunk_distances.push(distance);
+	}
+	chunk_distances.end_vector();
+	vec.end_vector();
+	Ok(fbb.view().to_vec())
+}
+fn field_gap_flex(node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "other field"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let other_field = calling_client
+		.scenegraph
+		.get_node(flex_vec.idx(1).as_str())
+		.ok_or_else(|| anyhow!("Other field node does not exist"))?
+		.field
+		.get()
+		.ok_or_else(|| anyhow!("Other field node is not a field"))?
+		.clone();
+
+	let gap = node
+		.field
+		.get()
+		.unwrap()
+		.gap(reference_space.as_ref(), &**other_field);
+	Ok(FlexBuffable::from(gap).build_singleton())
+}
+fn field_seam_point_flex(node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "other field", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let other_field = calling_client
+		.scenegraph
+		.get_node(flex_vec.idx(1).as_str())
+		.ok_or_else(|| anyhow!("Other field node does not exist"))?
+		.field
+		.get()
+		.ok_or_else(|| anyhow!("Other field node is not a field"))?
+		.clone();
+	let point = flex_to_finite_vec3(flex_vec.idx(2), "Point")?;
+
+	let seam_point =
+		node.field
+			.get()
+			.unwrap()
+			.seam_point(reference_space.as_ref(), &**other_field, point);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		if let Some(seam_point) = seam_point {
+			push_to_vec!(vec, mint::Vector3::from(seam_point));
+		}
+	}))
+}
+fn field_plane_closest_point_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(
+		data,
+		&["reference space", "point", "plane origin", "plane normal"],
+	)?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+	let plane_origin = flex_to_finite_vec3(flex_vec.idx(2), "Plane origin")?;
+	let plane_normal = flex_to_finite_vec3(flex_vec.idx(3), "Plane normal")?;
+
+	let closest_point = node.field.get().unwrap().plane_closest_point(
+		reference_space.as_ref(),
+		point,
+		plane_origin,
+		plane_normal,
+	);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		if let Some(closest_point) = closest_point {
+			push_to_vec!(vec, mint::Vector3::from(closest_point));
+		}
+	}))
+}
+fn field_contained_in_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["other field"])?;
+	let other_field = calling_client
+		.scenegraph
+		.get_node(flex_vec.idx(0).as_str())
+		.ok_or_else(|| anyhow!("Other field node does not exist"))?
+		.field
+		.get()
+		.ok_or_else(|| anyhow!("Other field node is not a field"))?
+		.clone();
+	let count = if flex_vec.idx(1).flexbuffer_type().is_null() {
+		CONTAINMENT_SAMPLES
+	} else {
+		flex_vec.idx(1).as_u32() as usize
+	};
+	ensure!(
+		count <= MAX_SURFACE_SAMPLES,
+		"Too many surface samples requested (max {})",
+		MAX_SURFACE_SAMPLES
+	);
+	let seed = flex_to_sample_seed(flex_vec.idx(2));
+
+	let contained = node
+		.field
+		.get()
+		.unwrap()
+		.contained_in(&**other_field, count, seed);
+	Ok(FlexBuffable::from(contained).build_singleton())
+}
+fn field_get_matrix_flex(node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<Vec<u8>> {
+	let root = flexbuffers::Reader::get_root(data)?;
+	let reference_space = get_reference_space_flex(&calling_client, root.as_str())?;
+
+	let matrix = Spatial::space_to_space_matrix(
+		Some(reference_space.as_ref()),
+		Some(node.field.get().unwrap().spatial_ref()),
+	);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for value in matrix.to_cols_array() {
+			vec.push(value);
+		}
+	}))
+}
+fn field_set_scale_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+	let scale = flex_to_scale(flexbuffers::Reader::get_root(data)?)?;
+	let spatial = node.field.get().unwrap().spatial_ref();
+	let (_, rotation, translation) = spatial.local_transform().to_scale_rotation_translation();
+	spatial.set_local_transform(Mat4::from_scale_rotation_translation(
+		Vec3::splat(scale),
+		rotation,
+		translation,
+	));
+	Ok(())
+}
+fn field_aim_at_flex(node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+	let flex_vec = flex_args(data, &["reference space", "target"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let target = flex_to_finite_vec3(flex_vec.idx(1), "Target")?;
+
+	let field = node.field.get().unwrap();
+	ensure!(
+		field.local_natural_axis().is_some(),
+		"Field has no natural axis to aim"
+	);
+	let rotation = field
+		.aim_rotation(reference_space.as_ref(), target)
+		.ok_or_else(|| anyhow!("Target is at the field's origin"))?;
+	let spatial = field.spatial_ref();
+	spatial.set_local_transform(spatial.local_transform() * Mat4::from_quat(rotation));
+	Ok(())
+}
+/// The per field state every field kind has, so they all share one accessor for it.
+#[derive(Default)]
+pub struct FieldCommon {
+	pub stats: FieldStats,
+	pub cache: DistanceCache,
+	pub temporal_smoothing: DistanceSmoothing,
+	pub lod: FieldLod,
+}
+fn field_set_stats_enabled_flex(
+	node: &Node,
+	_calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	let root = flexbuffers::Reader::get_root(data)?;
+	node.field
+		.get()
+		.unwrap()
+		.common()
+		.stats
+		.set_enabled(root.as_bool());
+	Ok(())
+}
+fn field_get_stats_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<Vec<u8>> {
+	let root = flexbuffers::Reader::get_root(data)?;
+	let (evaluations, time) = node
+		.field
+		.get()
+		.unwrap()
+		.common()
+		.stats
+		.read(root.as_bool());
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		vec.push(evaluations);
+		vec.push(time.as_secs_f64());
+	}))
+}
+
+lazy_static! {
+	static ref FIELD_REGISTRY: Registry<Field> = Default::default();
+	pub static ref FIELD_METRICS: FieldMetrics = Default::default();
+}
+
+/// Server wide counts of field method calls, for seeing how much load fields put on a shared server.
+pub struct FieldMetrics {
+	queries: AtomicU64,
+	nanoseconds: AtomicU64,
+	since: Mutex<Instant>,
+}
+impl Default for FieldMetrics {
+	fn default() -> Self {
+		FieldMetrics {
+			queries: Default::default(),
+			nanoseconds: Default::default(),
+			since: Mutex::new(Instant::now()),
+		}
+	}
+}
+impl FieldMetrics {
+	pub fn record(&self, time: Duration) {
+		self.queries.fetch_add(1, Ordering::Relaxed);
+		self.nanoseconds
+			.fetch_add(time.as_nanos() as u64, Ordering::Relaxed);
+	}
+	/// Fields across all clients, queries since the last reset (or server start),
+	/// queries per second over that time and their average latency, optionally resetting.
+	pub fn read(&self, reset: bool) -> (usize, u64, f64, Duration) {
+		let fields = FIELD_REGISTRY.get_valid_contents().len();
+		let mut since = self.since.lock();
+		let elapsed = since.elapsed();
+		let (queries, nanoseconds) = if reset {
+			*since = Instant::now();
+			(
+				self.queries.swap(0, Ordering::Relaxed),
+				self.nanoseconds.swap(0, Ordering::Relaxed),
+			)
+		} else {
+			(
+				self.queries.load(Ordering::Relaxed),
+				self.nanoseconds.load(Ordering::Relaxed),
+			)
+		};
+		let average_latency = Duration::from_nanos(nanoseconds.checked_div(queries).unwrap_or(0));
+		(
+			fields,
+			queries,
+			queries as f64 / elapsed.as_secs_f64(),
+			average_latency,
+		)
+	}
+}
+
+/// Runs a field query, counting it and its latency towards the server wide metrics.
+fn field_query(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+	name: &str,
+	query: Method,
+) -> Result<Vec<u8>> {
+	let start = Instant::now();
+	let result = query(node, calling_client.clone(), data);
+	FIELD_METRICS.record(start.elapsed());
+	if let Err(error) = &result {
+		log_field_error("method", name, node, &calling_client, error);
+	}
+	result
+}
+fn field_signal(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+	name: &str,
+	signal: Signal,
+) -> Result<()> {
+	let result = signal(node, calling_client.clone(), data);
+	if let Err(error) = &result {
+		log_field_error("signal", name, node, &calling_client, error);
+	}
+	result
+}
+/// Clients often drop the errors of queries they send every frame, so say what failed and for who.
+fn log_field_error(
+	kind: &str,
+	name: &str,
+	node: &Node,
+	calling_client: &Client,
+	error: &anyhow::Error,
+) {
+	log::warn!(
+		"Field {} {} on {} failed for client {}: {}",
+		kind,
+		name,
+		node.get_path(),
+		calling_client.uid,
+		error
+	);
+}
+
+pub fn get_metrics_flex(
+	_node: &Node,
+	_calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let root = flexbuffers::Reader::get_root(data)?;
+	let (fields, queries, queries_per_second, average_latency) = FIELD_METRICS.read(root.as_bool());
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		vec.push(fields as u64);
+		vec.push(queries);
+		vec.push(queries_per_second);
+		vec.push(average_latency.as_secs_f64());
+	}))
+}
+
+pub enum Field {
+	Box(BoxField),
+	RoundedBox(RoundedBoxField),
+	Quad(QuadField),
+	Cylinder(CylinderField),
+	Sphere(SphereField),
+	Cone(ConeField),
+	CappedTorus(CappedTorusField),
+	TaperedCapsule(TaperedCapsuleField),
+	Pyramid(PyramidField),
+	PolylineCapsule(PolylineCapsuleField),
+	Wedge(WedgeField),
+	Octahedron(OctahedronField),
+	Slab(SlabField),
+	Mesh(MeshField),
+	Compound(CompoundField),
+	Mirror(MirrorField),
+	BlendShape(BlendShapeField),
+	Scale(ScaleField),
+	NoiseWarp(NoiseWarpField),
+	Clip(ClipField),
+	Union(UnionField),
+}
+
+impl Drop for Field {
+	fn drop(&mut self) {
+		FIELD_REGISTRY.remove(self);
+		// composite fields of this one now measure without it
+		DistanceCache::invalidate_all();
+	}
+}
+
+impl Deref for Field {
+	type Target = dyn FieldTrait;
+	fn deref(&self) -> &Self::Target {
+		match self {
+			Field::Box(field) => field,
+			Field::RoundedBox(field) => field,
+			Field::Quad(field) => field,
+			Field::Cylinder(field) => field,
+			Field::Sphere(field) => field,
+			Field::Cone(field) => field,
+			Field::CappedTorus(field) => field,
+			Field::TaperedCapsule(field) => field,
+			Field::Pyramid(field) => field,
+			Field::PolylineCapsule(field) => field,
+			Field::Wedge(field) => field,
+			Field::Octahedron(field) => field,
+			Field::Slab(field) => field,
+			Field::Mesh(field) => field,
+			Field::Compound(field) => field,
+			Field::Mirror(field) => field,
+			Field::BlendShape(field) => field,
+			Field::Scale(field) => field,
+			Field::NoiseWarp(field) => field,
+			Field::Clip(field) => field,
+			Field::Union(field) => field,
+		}
+	}
+}
+
+fn upgrade_child(child: &Weak<Field>) -> Result<Arc<Field>> {
+	child
+		.upgrade()
+		.ok_or_else(|| anyhow!("Child field no longer exists"))
+}
+
+impl Field {
+	/// The axis the shape is naturally held or aimed along in its own space, like a cylinder's
+	/// length or a box's longest side. `None` for shapes without one, like spheres and
+	/// composites. Symmetric shapes could as well have it pointing the other way.
+	pub fn local_natural_axis(&self) -> Option<Vec3A> {
+		match self {
+			Field::Box(field) => dominant_axis(*field.size.lock()),
+			Field::RoundedBox(field) => dominant_axis(field.size.lock().0),
+			Field::Quad(field) => {
+				let (width, height, _) = *field.size.lock();
+				dominant_axis(vec3(width, height, 0_f32))
+			}
+			Field::Cylinder(_) | Field::Cone(_) | Field::CappedTorus(_) => Some(Vec3A::Z),
+			Field::TaperedCapsule(_) | Field::Pyramid(_) | Field::Wedge(_) | Field::Slab(_) => {
+				Some(Vec3A::Y)
+			}
+			Field::PolylineCapsule(field) => {
+				let axis = (*field.points.last().unwrap() - field.points[0]).normalize_or_zero();
+				(axis != Vec3A::ZERO).then(|| axis)
+			}
+			_ => None,
+		}
+	}
+	/// `local_natural_axis` in the reference space.
+	pub fn natural_axis(&self, reference_space: &Spatial) -> Option<Vec3A> {
+		let local_to_reference_space =
+			Spatial::space_to_space_matrix(Some(self.spatial_ref()), Some(reference_space));
+		self.local_natural_axis().map(|axis| {
+			local_to_reference_space
+				.transform_vector3a(axis)
+				.normalize()
+		})
+	}
+	/// Local rotation turning the field's natural axis (whichever way is closer) to point
+	/// from its origin at `target`. `None` if the target is at the origin or there's no axis.
+	pub fn aim_rotation(&self, reference_space: &Spatial, target: Vec3A) -> Option<Quat> {
+		let direction = self
+			.local_point(reference_space, target)
+			.normalize_or_zero();
+		if direction == Vec3A::ZERO {
+			return None;
+		}
+		let axis = self.local_natural_axis()?;
+		let axis = if axis.dot(direction) < 0_f32 {
+			-axis
+		} else {
+			axis
+		};
+		Some(Quat::from_rotation_arc(axis.into(), direction.into()))
+	}
+	/// Attaches a field with the same shape to `node`, which must already have a spatial.
+	/// Meshes share their data with this field and operators share their children.
+	pub fn clone_to(&self, node: &Arc<Node>) -> Result<()> {
+		match self {
+			Field::Box(field) => BoxField::add_to(node, *field.size.lock()),
+			Field::RoundedBox(field) => {
+				let (size, rounding) = *field.size.lock();
+				RoundedBoxField::add_to(node, size, rounding)
+			}
+			Field::Quad(field) => {
+				let (width, height, radius) = *field.size.lock();
+				QuadField::add_to(node, width, height, radius)
+			}
+			Field::Cylinder(field) => {
+				let (length, radius) = *field.size.lock();
+				CylinderField::add_to(node, length, radius)
+			}
+			Field::Sphere(field) => SphereField::add_to(node, field.radius.load(Ordering::Relaxed)),
+			Field::Cone(field) => ConeField::add_to(
+				node,
+				field.angle.load(Ordering::Relaxed),
+				field.length.load(Ordering::Relaxed),
+			),
+			Field::CappedTorus(field) => CappedTorusField::add_to(
+				node,
+				field.major_radius.load(Ordering::Relaxed),
+				field.minor_radius.load(Ordering::Relaxed),
+				field.angle.load(Ordering::Relaxed),
+			),
+			Field::TaperedCapsule(field) => {
+				let (bottom_radius, top_radius, length) = *field.size.lock();
+				TaperedCapsuleField::add_to(node, bottom_radius, top_radius, length)
+			}
+			Field::Pyramid(field) => {
+				let (half_width, height) = *field.size.lock();
+				PyramidField::add_to(node, half_width, height)
+			}
+			Field::PolylineCapsule(field) => PolylineCapsuleField::add_to(
+				node,
+				field.points.clone(),
+				field.radius.load(Ordering::Relaxed),
+			),
+			Field::Wedge(field) => {
+				let (start_angle, end_angle, radius, height) = *field.size.lock();
+				WedgeField::add_to(node, start_angle, end_angle, radius, height)
+			}
+			Field::Octahedron(field) => {
+				OctahedronField::add_to(node, field.size.load(Ordering::Relaxed))
+			}
+			Field::Slab(field) => SlabField::add_to(node, field.thickness.load(Ordering::Relaxed)),
+			Field::Mesh(field) => MeshField::add_to(
+				node,
+				field.vertices.clone(),
+				field.triangles.clone(),
+				field.materials.clone(),
+			),
+			Field::Compound(field) => CompoundField::add_to(
+				node,
+				field.operator,
+				field
+					.children
+					.iter()
+					.map(|(offset, shape)| (offset.inverse(), shape.clone()))
+					.collect(),
+			),
+			Field::Mirror(field) => {
+				MirrorField::add_to(node, &upgrade_child(&field.child)?, field.axis)
+			}
+			Field::BlendShape(field) => BlendShapeField::add_to(
+				node,
+				&upgrade_child(&field.from)?,
+				&upgrade_child(&field.to)?,
+				field.blend.load(Ordering::Relaxed),
+			),
+			Field::Scale(field) => ScaleField::add_to(
+				node,
+				&upgrade_child(&field.child)?,
+				field.scale.load(Ordering::Relaxed),
+			),
+			Field::NoiseWarp(field) => {
+				let (frequency, amplitude) = *field.noise.lock();
+				NoiseWarpField::add_to(
+					node,
+					&upgrade_child(&field.child)?,
+					frequency,
+					amplitude,
+					field.seed,
+				)
+			}
+			Field::Clip(field) => {
+				ClipField::add_to(node, &upgrade_child(&field.child)?, *field.size.lock())
+			}
+			Field::Union(field) => {
+				let children = field
+					.children
+					.iter()
+					.map(upgrade_child)
+					.collect::<Result<Vec<_>>>()?;
+				let (kind, smoothness) = *field.smoothing.lock();
+				UnionField::add_to(node, &children, smoothness)?;
+				if let Field::Union(union_field) = node.field.get().unwrap().as_ref() {
+					union_field.set_smoothing_kind(kind);
+				}
+				Ok(())
+			}
+		}
+	}
+}
+
+/// Where every field node is created, so they can all be found (and cleaned up) by path.
+pub const FIELD_PARENT_PATH: &str = "/field";
+
+pub fn create_interface(client: &Arc<Client>) {
+	let node = Node::create(client, "", "field", false);
+	node.add_local_signal("createBoxField", create_box_field_flex);
+	node.add_local_signal(
+		"createBoxFieldFromPoints",
+		create_box_field_from_points_flex,
+	);
+	node.add_local_signal("createRoundedBoxField", create_rounded_box_field_flex);
+	node.add_local_signal("createQuadField", create_quad_field_flex);
+	node.add_local_signal("createCylinderField", create_cylinder_field_flex);
+	node.add_local_signal("createSphereField", create_sphere_field_flex);
+	node.add_local_signal(
+		"createSphereFieldBounding",
+		create_sphere_field_bounding_flex,
+	);
+	node.add_local_signal("createConeField", create_cone_field_flex);
+	node.add_local_signal("createSpotlightField", create_spotlight_field_flex);
+	node.add_local_signal("createCappedTorusField", create_capped_torus_field_flex);
+	node.add_local_signal(
+		"createTaperedCapsuleField",
+		create_tapered_capsule_field_flex,
+	);
+	node.add_local_signal("createPyramidField", create_pyramid_field_flex);
+	node.add_local_signal(
+		"createPolylineCapsuleField",
+		create_polyline_capsule_field_flex,
+	);
+	node.add_local_signal("createWedgeField", create_wedge_field_flex);
+	node.add_local_signal("createOctahedronField", create_octahedron_field_flex);
+	node.add_local_signal("createSlabField", create_slab_field_flex);
+	node.add_local_signal("createMeshField", create_mesh_field_flex);
+	node.add_local_signal("createCompoundField", create_compound_field_flex);
+	node.add_local_signal("createMirrorField", create_mirror_field_flex);
+	node.add_local_signal("createBlendShapeField", create_blend_shape_field_flex);
+	node.add_local_signal("createScaleField", create_scale_field_flex);
+	node.add_local_signal("createNoiseWarpField", create_noise_warp_field_flex);
+	node.add_local_signal("createClipField", create_clip_field_flex);
+	node.add_local_signal("createUnionField", create_union_field_flex);
+	node.add_local_signal("cloneField", clone_field_flex);
+	node.add_local_method("createFields", create_fields_flex);
+	node.add_local_method("fieldsContaining", fields_containing_flex);
+	node.add_local_method("getMetrics", get_metrics_flex);
+	node.add_local_method("fieldDistances", field_distances_flex);
+	node.add_local_method("cylinderSelfTest", cylinder_self_test_flex);
+	node.add_to_scenegraph();
+}
+
+/// Every field of the client that `p` is inside of, sorted by path so the order is stable.
+pub fn fields_containing(client: &Client, reference_space: &Spatial, p: Vec3A) -> Vec<Arc<Node>> {
+	let mut fields: Vec<Arc<Node>> = client
+		.scenegraph
+		.get_nodes()
+		.into_iter()
+		.filter(|node| {
+			node.field.get().map_or(false, |field| {
+				let local_p = field.local_point(reference_space, p);
+				local_p.length() <= field.local_bounding_radius()
+					&& field.local_distance(local_p) < 0_f32
+			})
+		})
+		.collect();
+	fields.sort_unstable_by(|a, b| a.get_path().cmp(b.get_path()));
+	fields
+}
+
+pub fn fields_containing_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let fields = fields_containing(&calling_client, reference_space.as_ref(), point);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for field in fields {
+			vec.push(field.get_path());
+		}
+	}))
+}
+
+/// Distance from `p` to each of `fields`, only resolving the reference space's transform once
+/// instead of once per field like `FieldTrait::distance` would.
+pub fn field_distances(reference_space: &Spatial, p: Vec3A, fields: &[Arc<Field>]) -> Vec<f32> {
+	let reference_to_world_space = reference_space.global_transform();
+	fields
+		.iter()
+		.map(|field| {
+			let world_to_local_space = field.spatial_ref().global_transform().inverse();
+			field.distance_from_matrix(world_to_local_space * reference_to_world_space, p)
+		})
+		.collect()
+}
+
+pub fn field_distances_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point", "fields"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+	let fields = flex_vec
+		.idx(2)
+		.get_vector()?
+		.iter()
+		.map(|path| get_child_field_flex(&calling_client, path.as_str()))
+		.collect::<Result<Vec<_>>>()?;
+
+	let distances = field_distances(reference_space.as_ref(), point, &fields);
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for distance in distances {
+			vec.push(distance);
+		}
+	}))
+}
+
+/// Creates a field from a kind (the shape in the `create*Field` signal names, e.g. "Box")
+/// and the same arguments that signal takes.
+fn create_field(
+	calling_client: &Arc<Client>,
+	kind: &str,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	match kind {
+		"Box" => create_box_field(calling_client, flex_vec),
+		"BoxFromPoints" => create_box_field_from_points(calling_client, flex_vec),
+		"RoundedBox" => create_rounded_box_field(calling_client, flex_vec),
+		"Quad" => create_quad_field(calling_client, flex_vec),
+		"Cylinder" => create_cylinder_field(calling_client, flex_vec),
+		"Sphere" => create_sphere_field(calling_client, flex_vec),
+		"SphereBounding" => create_sphere_field_bounding(calling_client, flex_vec),
+		"Cone" => create_cone_field(calling_client, flex_vec),
+		"Spotlight" => create_spotlight_field(calling_client, flex_vec),
+		"CappedTorus" => create_capped_torus_field(calling_client, flex_vec),
+		"TaperedCapsule" => create_tapered_capsule_field(calling_client, flex_vec),
+		"Pyramid" => create_pyramid_field(calling_client, flex_vec),
+		"PolylineCapsule" => create_polyline_capsule_field(calling_client, flex_vec),
+		"Wedge" => create_wedge_field(calling_client, flex_vec),
+		"Octahedron" => create_octahedron_field(calling_client, flex_vec),
+		"Slab" => create_slab_field(calling_client, flex_vec),
+		"Mesh" => create_mesh_field(calling_client, flex_vec),
+		"Compound" => create_compound_field(calling_client, flex_vec),
+		"Mirror" => create_mirror_field(calling_client, flex_vec),
+		"BlendShape" => create_blend_shape_field(calling_client, flex_vec),
+		"Scale" => create_scale_field(calling_client, flex_vec),
+		"NoiseWarp" => create_noise_warp_field(calling_client, flex_vec),
+		"Clip" => create_clip_field(calling_client, flex_vec),
+		"Union" => create_union_field(calling_client, flex_vec),
+		_ => Err(anyhow!("Unknown field kind {}", kind)),
+	}
+}
+
+/// Creates many fields in one round trip. Each descriptor is `[kind, arguments]` and the
+/// result has an error message per descriptor, empty if that field was created.
+pub fn create_fields_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let descriptors = flexbuffers::Reader::get_root(data)?.get_vector()?;
+	let results: Vec<Result<()>> = descriptors
+		.iter()
+		.map(|descriptor| {
+			let descriptor = descriptor.get_vector()?;
+			create_field(
+				&calling_client,
+				descriptor.idx(0).get_str()?,
+				descriptor.idx(1).get_vector()?,
+			)
+		})
+		.collect();
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for result in results {
+			match result {
+				Ok(()) => vec.push(""),
+				Err(error) => vec.push(error.to_string().as_str()),
+			}
+		}
+	}))
+}
+
+/// A new field node with the same shape as an existing one but its own transform.
+pub fn clone_field_flex(_node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+	let flex_vec = flex_args(
+		data,
+		&["source field", "name", "parent", "position", "rotation"],
+	)?;
+	let source = calling_client
+		.scenegraph
+		.get_node(flex_vec.idx(0).as_str())
+		.ok_or_else(|| anyhow!("Source field node does not exist"))?
+		.field
+		.get()
+		.ok_or_else(|| anyhow!("Source field node is not a field"))?
+		.clone();
+	let node = Node::create(
+		&calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(1).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(&calling_client, flex_vec.idx(2).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(4))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(3), "Position")?.into(),
+	);
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	source.clone_to(&node)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::nodes::root::Root;
+	use libstardustxr::scenegraph::ScenegraphError;
+
+	pub(super) fn test_client() -> Arc<Client> {
+		let client = Client::new_local();
+		let _ = client.root.set(Root::create(&client));
+		create_interface(&client);
+		client
+	}
+	pub(super) fn signal(client: &Arc<Client>, path: &str, method: &str, data: &[u8]) {
+		client
+			.scenegraph
+			.get_node(path)
+			.unwrap()
+			.send_local_signal(client.clone(), method, data)
+			.unwrap();
+	}
+	pub(super) fn method(
+		client: &Arc<Client>,
+		path: &str,
+		method: &str,
+		data: &[u8],
+	) -> Result<Vec<u8>, ScenegraphError> {
+		client
+			.scenegraph
+			.get_node(path)
+			.unwrap()
+			.execute_local_method(client.clone(), method, data)
+	}
+	pub(super) fn create_sphere(
+		client: &Arc<Client>,
+		name: &str,
+		position: Vec3,
+		radius: f32,
+	) -> Arc<Node> {
+		signal(
+			client,
+			"/field",
+			"createSphereField",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push(name);
+				vec.push("/");
+				push_to_vec!(vec, mint::Vector3::from(position));
+				vec.push(radius);
+			}),
+		);
+		client
+			.scenegraph
+			.get_node(&format!("/field/{}", name))
+			.unwrap()
+	}
+	/// Creates a field through the `/field` interface whose arguments start with a name,
+	/// parent, position and rotation, with `args` pushing the rest.
+	pub(super) fn try_create_field(
+		client: &Arc<Client>,
+		create_signal: &str,
+		name: &str,
+		position: Vec3,
+		args: impl FnOnce(&mut flexbuffers::VectorBuilder),
+	) -> Result<(), ScenegraphError> {
+		client
+			.scenegraph
+			.get_node("/field")
+			.unwrap()
+			.send_local_signal(
+				client.clone(),
+				create_signal,
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push(name);
+					vec.push("/");
+					push_to_vec!(
+						vec,
+						mint::Vector3::from(position),
+						mint::Quaternion::from(Quat::IDENTITY)
+					);
+					args(vec);
+				}),
+			)
+	}
+	pub(super) fn create_field(
+		client: &Arc<Client>,
+		create_signal: &str,
+		name: &str,
+		position: Vec3,
+		args: impl FnOnce(&mut flexbuffers::VectorBuilder),
+	) -> Arc<Node> {
+		try_create_field(client, create_signal, name, position, args).unwrap();
+		client
+			.scenegraph
+			.get_node(&format!("/field/{}", name))
+			.unwrap()
+	}
+	pub(super) fn distance_at(client: &Arc<Client>, node: &Node, point: Vec3) -> f32 {
+		read_f32(&method(client, node.get_path(), "distance", &point_args(point)).unwrap())
+	}
+	pub(super) fn create_box(
+		client: &Arc<Client>,
+		name: &str,
+		position: Vec3,
+		size: Vec3,
+	) -> Arc<Node> {
+		signal(
+			client,
+			"/field",
+			"createBoxField",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push(name);
+				vec.push("/");
+				push_to_vec!(
+					vec,
+					mint::Vector3::from(position),
+					mint::Quaternion::from(Quat::IDENTITY),
+					mint::Vector3::from(size)
+				);
+			}),
+		);
+		client
+			.scenegraph
+			.get_node(&format!("/field/{}", name))
+			.unwrap()
+	}
+	pub(super) fn read_f32(result: &[u8]) -> f32 {
+		flexbuffers::Reader::get_root(result).unwrap().as_f32()
+	}
+	pub(super) fn read_vec3s(result: &[u8]) -> Vec<Vec3> {
+		let flex_vec = flexbuffers::Reader::get_root(result).unwrap().as_vector();
+		flex_vec
+			.iter()
+			.map(|flex| flex_to_vec3!(flex).unwrap().into())
+			.collect()
+	}
+	pub(super) fn point_args(point: Vec3) -> Vec<u8> {
+		flexbuffer_from_vector_arguments(|vec| {
+			vec.push("/");
+			push_to_vec!(vec, mint::Vector3::from(point));
+		})
+	}
+	pub(super) fn query_args(
+		point: Vec3,
+		options: impl FnOnce(&mut flexbuffers::MapBuilder),
+	) -> Vec<u8> {
+		flexbuffer_from_vector_arguments(|vec| {
+			vec.push("/");
+			push_to_vec!(vec, mint::Vector3::from(point));
+			let mut map = vec.start_map();
+			options(&mut map);
+			map.end_map();
+		})
+	}
+	pub(super) fn assert_near(value: f32, expected: f32, tolerance: f32) {
+		assert!(
+			(value - expected).abs() <= tolerance,
+			"{} is not within {} of {}",
+			value,
+			tolerance,
+			expected
+		);
+	}
+
+	#[test]
+	pub(super) fn stats_count_evaluations() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "profiled", Vec3::ZERO, 0.5_f32);
+		let path = sphere.get_path();
+		for _ in 0..3 {
+			method(&client, path, "distance", &point_args(Vec3::X)).unwrap();
+		}
+		signal(
+			&client,
+			path,
+			"setStatsEnabled",
+			&flexbuffers::singleton(true),
+		);
+		for i in 0..10 {
+			method(&client, path, "distance", &point_args(Vec3::X * i as f32)).unwrap();
+		}
+		let read_stats = |reset: bool| {
+			let stats = method(&client, path, "getStats", &flexbuffers::singleton(reset)).unwrap();
+			let stats = flexbuffers::Reader::get_root(stats.as_slice())
+				.unwrap()
+				.as_vector();
+			(stats.idx(0).as_u64(), stats.idx(1).as_f64())
+		};
+		let (evaluations, time) = read_stats(true);
+		assert_eq!(evaluations, 10);
+		assert!(time > 0_f64);
+		assert_eq!(read_stats(false).0, 0);
+	}
+
+	#[test]
+	pub(super) fn box_tangent_frame_is_orthonormal() {
+		let client = test_client();
+		let box_node = create_box(&client, "framed", Vec3::ZERO, Vec3::ONE);
+		let result = method(
+			&client,
+			box_node.get_path(),
+			"tangent_frame",
+			&point_args(vec3(0.5_f32, 0.1_f32, -0.2_f32)),
+		)
+		.unwrap();
+		let frame = read_vec3s(&result);
+		assert_eq!(frame.len(), 3);
+		assert!(frame[0].abs_diff_eq(Vec3::X, 0.001_f32));
+		for (i, a) in frame.iter().enumerate() {
+			assert_near(a.length(), 1_f32, 0.001_f32);
+			for b in &frame[i + 1..] {
+				assert_near(a.dot(*b), 0_f32, 0.001_f32);
+			}
+		}
+	}
+
+	#[test]
+	pub(super) fn box_corner_contacts_are_on_adjacent_faces() {
+		let client = test_client();
+		let box_node = create_box(&client, "contact", Vec3::ZERO, Vec3::ONE);
+		let result = method(
+			&client,
+			box_node.get_path(),
+			"contact_points",
+			&point_args(Vec3::splat(0.45_f32)),
+		)
+		.unwrap();
+		let contact_points = read_vec3s(&result);
+		assert!(contact_points.len() > 1);
+		for contact_point in &contact_points {
+			assert_near(
+				box_distance(Vec3A::from(*contact_point), Vec3::ONE),
+				0_f32,
+				0.0001_f32,
+			);
+		}
+		for axis in 0..3 {
+			assert!(contact_points
+				.iter()
+				.any(|contact_point| (contact_point[axis] - 0.5_f32).abs() < 0.0001_f32));
+		}
+	}
+
+	#[test]
+	pub(super) fn surface_samples_cover_the_sphere() {
+		let client = test_client();
+		let center = vec3(1_f32, 0_f32, 0_f32);
+		let sphere = create_sphere(&client, "sampled", center, 0.5_f32);
+		let data = flexbuffer_from_vector_arguments(|vec| {
+			vec.push("/");
+			vec.push(64_u32);
+		});
+		let samples =
+			read_vec3s(&method(&client, sphere.get_path(), "sample_surface", &data).unwrap());
+		assert_eq!(samples.len(), 64);
+		let mut octants = [false; 8];
+		for sample in samples {
+			let offset = sample - center;
+			assert_near(offset.length(), 0.5_f32, 0.001_f32);
+			octants[Vec3A::from(offset).cmpgt(Vec3A::ZERO).bitmask() as usize] = true;
+		}
+		assert!(octants.iter().all(|covered| *covered));
+	}
+
+	#[test]
+	pub(super) fn surface_samples_lie_on_a_box() {
+		let client = test_client();
+		let box_node = create_box(&client, "sampled", Vec3::ZERO, Vec3::ONE);
+		let data = flexbuffer_from_vector_arguments(|vec| {
+			vec.push("/");
+			vec.push(32_u32);
+		});
+		let samples =
+			read_vec3s(&method(&client, box_node.get_path(), "sample_surface", &data).unwrap());
+		for sample in samples {
+			assert_near(sample.abs().max_element(), 0.5_f32, 0.0001_f32);
+		}
+	}
+
+	pub(super) fn gap_args(other: &Node) -> Vec<u8> {
+		flexbuffer_from_vector_arguments(|vec| {
+			vec.push("/");
+			vec.push(other.get_path());
+		})
+	}
+
+	#[test]
+	pub(super) fn gap_between_spheres() {
+		let client = test_client();
+		let a = create_sphere(&client, "a", Vec3::ZERO, 0.5_f32);
+		let b = create_sphere(&client, "b", vec3(2_f32, 1_f32, 0_f32), 0.25_f32);
+		let gap = read_f32(&method(&client, a.get_path(), "gap", &gap_args(&b)).unwrap());
+		assert_near(gap, 5_f32.sqrt() - 0.75_f32, 0.001_f32);
+	}
+
+	#[test]
+	pub(super) fn gap_between_box_and_sphere() {
+		let client = test_client();
+		let box_node = create_box(&client, "box", Vec3::ZERO, Vec3::ONE);
+		let sphere = create_sphere(&client, "sphere", vec3(0_f32, 2_f32, 0_f32), 0.25_f32);
+		let gap =
+			read_f32(&method(&client, box_node.get_path(), "gap", &gap_args(&sphere)).unwrap());
+		assert_near(gap, 1.25_f32, 0.001_f32);
+	}
+
+	#[test]
+	pub(super) fn seam_of_intersecting_spheres() {
+		let client = test_client();
+		let a = create_sphere(&client, "a", Vec3::ZERO, 1_f32);
+		let b = create_sphere(&client, "b", Vec3::X, 1_f32);
+		let seam_args = |other: &Node| {
+			flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				vec.push(other.get_path());
+				push_to_vec!(vec, mint::Vector3::from(vec3(0.5_f32, 1.2_f32, 0.3_f32)));
+			})
+		};
+		let seam =
+			read_vec3s(&method(&client, a.get_path(), "seam_point", &seam_args(&b)).unwrap());
+		assert_eq!(seam.len(), 1);
+		// the spheres meet on a circle of radius sqrt(0.75) in the x = 0.5 plane
+		assert_near(seam[0].x, 0.5_f32, 0.001_f32);
+		assert_near(seam[0].yz().length(), 0.75_f32.sqrt(), 0.001_f32);
+
+		let far = create_sphere(&client, "far", Vec3::X * 3_f32, 0.5_f32);
+		let seam =
+			read_vec3s(&method(&client, a.get_path(), "seam_point", &seam_args(&far)).unwrap());
+		assert!(seam.is_empty());
+	}
+
+	#[test]
+	pub(super) fn seam_of_touching_boxes() {
+		let client = test_client();
+		let a = create_box(&client, "a", Vec3::ZERO, Vec3::ONE);
+		let b = create_box(&client, "b", Vec3::X, Vec3::ONE);
+		let data = flexbuffer_from_vector_arguments(|vec| {
+			vec.push("/");
+			vec.push(b.get_path());
+			push_to_vec!(vec, mint::Vector3::from(vec3(0.6_f32, 0.2_f32, 0.1_f32)));
+		});
+		let seam = read_vec3s(&method(&client, a.get_path(), "seam_point", &data).unwrap());
+		assert_eq!(seam.len(), 1);
+		assert!(seam[0].abs_diff_eq(vec3(0.5_f32, 0.2_f32, 0.1_f32), 0.001_f32));
+	}
+
+	pub(super) fn cylinder_size(node: &Node) -> (f32, f32) {
+		match node.field.get().unwrap().as_ref() {
+			Field::Cylinder(cylinder_field) => *cylinder_field.size.lock(),
+			_ => panic!("{} is not a cylinder", node.get_path()),
+		}
+	}
+
+	#[test]
+	pub(super) fn distance_with_missing_arguments_errors() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "sphere", Vec3::ZERO, 0.5_f32);
+		let no_arguments = flexbuffer_from_vector_arguments(|_| {});
+		let one_argument = flexbuffer_from_vector_arguments(|vec| vec.push("/"));
+		for data in [no_arguments, one_argument] {
+			match method(&client, sphere.get_path(), "distance", &data) {
+				Err(ScenegraphError::MethodError { error }) => {
+					assert!(error.to_string().contains("point"), "{}", error)
+				}
+				_ => panic!("distance didn't fail on missing arguments"),
+			}
+		}
+	}
+
+	#[test]
+	pub(super) fn create_fields_batch() {
+		let client = test_client();
+		let data = flexbuffer_from_vector_arguments(|vec| {
+			let mut descriptor = vec.start_vector();
+			descriptor.push("Box");
+			let mut args = descriptor.start_vector();
+			args.push("box");
+			args.push("/");
+			push_to_vec!(
+				&mut args,
+				mint::Vector3::from(Vec3::ZERO),
+				mint::Quaternion::from(Quat::IDENTITY),
+				mint::Vector3::from(vec3(1_f32, 2_f32, 3_f32))
+			);
+			args.end_vector();
+			descriptor.end_vector();
+
+			let mut descriptor = vec.start_vector();
+			descriptor.push("Sphere");
+			let mut args = descriptor.start_vector();
+			args.push("sphere");
+			args.push("/");
+			push_to_vec!(&mut args, mint::Vector3::from(Vec3::X * 5_f32));
+			args.push(0.5_f32);
+			args.end_vector();
+			descriptor.end_vector();
+
+			let mut descriptor = vec.start_vector();
+			descriptor.push("Cylinder");
+			let mut args = descriptor.start_vector();
+			args.push("cylinder");
+			args.push("/");
+			push_to_vec!(
+				&mut args,
+				mint::Vector3::from(Vec3::ZERO),
+				mint::Quaternion::from(Quat::IDENTITY)
+			);
+			args.push(2_f32);
+			args.push(0.25_f32);
+			args.end_vector();
+			descriptor.end_vector();
+		});
+		let results = method(&client, "/field", "createFields", &data).unwrap();
+		let results = flexbuffers::Reader::get_root(results.as_slice())
+			.unwrap()
+			.as_vector();
+		assert_eq!(results.len(), 3);
+		for result in results.iter() {
+			assert_eq!(result.as_str(), "");
+		}
+
+		let box_node = client.scenegraph.get_node("/field/box").unwrap();
+		assert_near(
+			distance_at(&client, &box_node, Vec3::Z * 2_f32),
+			0.5_f32,
+			0.0001_f32,
+		);
+		let sphere = client.scenegraph.get_node("/field/sphere").unwrap();
+		assert_near(
+			distance_at(&client, &sphere, Vec3::X * 4_f32),
+			0.5_f32,
+			0.0001_f32,
+		);
+		let cylinder = client.scenegraph.get_node("/field/cylinder").unwrap();
+		assert_eq!(cylinder_size(&cylinder), (2_f32, 0.25_f32));
+	}
+
+	pub(super) fn cache_hits(client: &Arc<Client>, node: &Node) -> u64 {
+		let hits = method(client, node.get_path(), "getCacheHits", &[]).unwrap();
+		flexbuffers::Reader::get_root(hits.as_slice())
+			.unwrap()
+			.as_u64()
+	}
+	/// Whether asking the same thing twice in a row hits the cache. Other tests moving and
+	/// resizing their fields make every cache stale, so give it a few tries.
+	pub(super) fn repeat_hits_cache(client: &Arc<Client>, node: &Node, query: impl Fn()) -> bool {
+		(0..100).any(|_| {
+			let hits = cache_hits(client, node);
+			query();
+			query();
+			cache_hits(client, node) > hits
+		})
+	}
+	pub(super) fn set_radius(client: &Arc<Client>, sphere: &Node, radius: f32) {
+		signal(
+			client,
+			sphere.get_path(),
+			"setRadius",
+			&flexbuffers::singleton(radius),
+		);
+	}
+
+	#[test]
+	pub(super) fn query_options_select_half_precision_on_every_query() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "half", Vec3::ZERO, 1_f32);
+		let path = sphere.get_path();
+		let half = |query: &str, point: Vec3| {
+			method(
+				&client,
+				path,
+				query,
+				&query_args(point, |options| options.push("half", true)),
+			)
+			.unwrap()
+		};
+		let from_half = |bits: u16| f16::from_bits(bits).to_f32();
+		let point = vec3(1.3_f32, 0.7_f32, -0.4_f32);
+
+		let distance = distance_at(&client, &sphere, point);
+		let half_distance = from_half(
+			flexbuffers::Reader::get_root(half("distance", point).as_slice())
+				.unwrap()
+				.as_u16(),
+		);
+		assert_near(
+			half_distance,
+			distance,
+			distance.abs() * f16::EPSILON.to_f32(),
+		);
+
+		for query in ["normal", "closest_point"] {
+			let full = method(&client, path, query, &point_args(point)).unwrap();
+			let full: Vec3 = flex_to_vec3!(flexbuffers::Reader::get_root(full.as_slice()).unwrap())
+				.unwrap()
+				.into();
+			let half_result = half(query, point);
+			let half_vec = flexbuffers::Reader::get_root(half_result.as_slice())
+				.unwrap()
+				.as_vector();
+			assert_eq!(half_vec.len(), 3);
+			for (axis, component) in half_vec.iter().enumerate() {
+				let component = from_half(component.as_u16());
+				assert_near(
+					component,
+					full[axis],
+					full[axis].abs() * f16::EPSILON.to_f32(),
+				);
+			}
+		}
+
+		// options can be combined, and reports come back as a map
+		let report = method(
+			&client,
+			path,
+			"distance",
+			&query_args(point, |options| {
+				options.push("half", true);
+				options.push("evaluations", true);
+			}),
+		)
+		.unwrap();
+		let report = flexbuffers::Reader::get_root(report.as_slice())
+			.unwrap()
+			.as_map();
+		assert_eq!(from_half(report.idx("distance").as_u16()), half_distance);
+		assert_eq!(report.idx("evaluations").as_u32(), 1);
+
+		let bad_metric = query_args(point, |options| options.push("metric", "taxicab"));
+		assert!(matches!(
+			method(&client, path, "distance", &bad_metric),
+			Err(ScenegraphError::MethodError { .. })
+		));
+	}
+
+	#[test]
+	pub(super) fn sphere_sweep_stops_short_of_the_surface_by_its_radius() {
+		let client = test_client();
+		let floor = create_box(
+			&client,
+			"floor",
+			-Vec3::Y * 0.5_f32,
+			vec3(10_f32, 1_f32, 10_f32),
+		);
+		let path = floor.get_path();
+		let sweep_args = |radius: f32| {
+			flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				push_to_vec!(
+					vec,
+					mint::Vector3::from(Vec3::Y),
+					mint::Vector3::from(-Vec3::Y),
+					radius
+				);
+			})
+		};
+		let time_of_impact = method(&client, path, "sphere_sweep", &sweep_args(0.1_f32)).unwrap();
+		let time_of_impact = flexbuffers::Reader::get_root(time_of_impact.as_slice())
+			.unwrap()
+			.as_vector()
+			.idx(0)
+			.as_f32();
+		assert_near(time_of_impact, 0.45_f32, 0.001_f32);
+
+		for radius in [-0.1_f32, f32::NAN, f32::INFINITY] {
+			assert!(matches!(
+				method(&client, path, "sphere_sweep", &sweep_args(radius)),
+				Err(ScenegraphError::MethodError { .. })
+			));
+		}
+	}
+
+	#[test]
+	pub(super) fn disconnecting_drops_the_clients_fields() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "left_behind", Vec3::ZERO, 1_f32);
+		let path = sphere.get_path().to_string();
+		let field = Arc::downgrade(sphere.field.get().unwrap());
+		let field_address = field.as_ptr() as usize;
+		let registered = || {
+			FIELD_REGISTRY
+				.get_valid_contents()
+				.iter()
+				.any(|field| Arc::as_ptr(field) as usize == field_address)
+		};
+		assert!(registered());
+		drop(sphere);
+
+		// the client itself can outlive its connection, its fields shouldn't
+		client.disconnect();
+		assert!(client.scenegraph.get_node(&path).is_none());
+		assert!(client.scenegraph.get_node(FIELD_PARENT_PATH).is_some());
+		assert!(field.upgrade().is_none());
+		assert!(!registered());
+	}
+	pub(super) fn query_at(client: &Arc<Client>, node: &Node, query: &str, point: Vec3) -> f32 {
+		read_f32(&method(client, node.get_path(), query, &point_args(point)).unwrap())
+	}
+
+	#[test]
+	pub(super) fn sphere_curvature_is_inverse_radius_and_plane_is_flat() {
+		let client = test_client();
+		for radius in [0.5_f32, 2_f32] {
+			let sphere = create_sphere(&client, &format!("curved_{}", radius), Vec3::ZERO, radius);
+			let on_surface = vec3(1_f32, 1_f32, 0_f32).normalize() * radius;
+			let curvature = query_at(&client, &sphere, "curvature", on_surface);
+			assert_near(curvature, 1_f32 / radius, 0.01_f32);
+		}
+		// the middle of a wide box's top face, well away from its edges
+		let plane = create_box(&client, "flat", Vec3::ZERO, vec3(4_f32, 1_f32, 4_f32));
+		let curvature = query_at(
+			&client,
+			&plane,
+			"curvature",
+			vec3(0.3_f32, 0.5_f32, -0.2_f32),
+		);
+		assert_near(curvature, 0_f32, 0.001_f32);
+	}
+
+	#[test]
+	pub(super) fn scaled_fields_answer_in_the_reference_spaces_units() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "doubled", Vec3::ZERO, 1_f32);
+		signal(
+			&client,
+			sphere.get_path(),
+			"setScale",
+			&flexbuffers::singleton(2_f32),
+		);
+
+		let on_surface = vec3(1_f32, 1_f32, 0_f32).normalize() * 2_f32;
+		let curvature = query_at(&client, &sphere, "curvature", on_surface);
+		assert_near(curvature, 0.5_f32, 0.01_f32);
+
+		let swept = method(
+			&client,
+			sphere.get_path(),
+			"distance_swept",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				push_to_vec!(
+					vec,
+					mint::Vector3::from(vec3(-6_f32, 4_f32, 0_f32)),
+					mint::Vector3::from(vec3(6_f32, 4_f32, 0_f32))
+				);
+			}),
+		)
+		.unwrap();
+		assert_near(read_f32(&swept), 2_f32, 0.001_f32);
+
+		let slice = method(
+			&client,
+			sphere.get_path(),
+			"slice",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				push_to_vec!(
+					vec,
+					mint::Vector3::from(Vec3::ZERO),
+					mint::Vector3::from(Vec3::X),
+					mint::Vector3::from(Vec3::Z),
+					6_f32,
+					6_f32,
+					3_u32,
+					3_u32
+				);
+			}),
+		)
+		.unwrap();
+		let rows = flexbuffers::Reader::get_root(slice.as_slice())
+			.unwrap()
+			.as_vector();
+		let sample = |x: usize, y: usize| rows.idx(y).as_vector().idx(x).as_f32();
+		assert_near(sample(1, 1), -2_f32, 0.0001_f32);
+		assert_near(sample(0, 2), 8_f32.sqrt() - 2_f32, 0.0001_f32);
+	}
+
+	#[test]
+	pub(super) fn local_space_results_skip_the_fields_rotation() {
+		let client = test_client();
+		let rotation = Quat::from_rotation_z(std::f32::consts::FRAC_PI_2);
+		signal(
+			&client,
+			"/field",
+			"createBoxField",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("turned");
+				vec.push("/");
+				push_to_vec!(
+					vec,
+					mint::Vector3::from(Vec3::ZERO),
+					mint::Quaternion::from(rotation),
+					mint::Vector3::from(vec3(2_f32, 1_f32, 1_f32))
+				);
+			}),
+		);
+		let path = "/field/turned";
+		let point = vec3(0_f32, 3_f32, 0.2_f32);
+		let read_vec3 = |result: Vec<u8>| -> Vec3 {
+			flex_to_vec3!(flexbuffers::Reader::get_root(result.as_slice()).unwrap())
+				.unwrap()
+				.into()
+		};
+		for (query, reference_result) in [
+			("normal", Vec3::Y),
+			("closest_point", vec3(0_f32, 1_f32, 0.2_f32)),
+		] {
+			let reference = read_vec3(method(&client, path, query, &point_args(point)).unwrap());
+			let local = read_vec3(
+				method(
+					&client,
+					path,
+					query,
+					&query_args(point, |options| options.push("local", true)),
+				)
+				.unwrap(),
+			);
+			assert!(
+				reference.abs_diff_eq(reference_result, 0.001_f32),
+				"{} {}",
+				query,
+				reference
+			);
+			// the box's long side is along its local X, which the rotation turns to Y
+			let expected_local = rotation.inverse() * reference;
+			assert!(
+				local.abs_diff_eq(expected_local, 0.001_f32),
+				"{} {}",
+				query,
+				local
+			);
+			assert!(!local.abs_diff_eq(reference, 0.1_f32));
+		}
+	}
+
+	#[test]
+	pub(super) fn non_finite_points_are_rejected() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "finite", Vec3::ZERO, 1_f32);
+		let path = sphere.get_path();
+		for query in ["distance", "normal", "closest_point"] {
+			for bad in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+				match method(&client, path, query, &point_args(vec3(0_f32, bad, 1_f32))) {
+					Err(ScenegraphError::MethodError { error }) => {
+						assert!(
+							error.to_string().contains("non-finite"),
+							"{}: {}",
+							query,
+							error
+						)
+					}
+					_ => panic!("{} accepted a non-finite point", query),
+				}
+			}
+		}
+
+		let created = client
+			.scenegraph
+			.get_node("/field")
+			.unwrap()
+			.send_local_signal(
+				client.clone(),
+				"createSphereField",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push("nan_sphere");
+					vec.push("/");
+					push_to_vec!(
+						vec,
+						mint::Vector3::from(vec3(f32::NAN, 0_f32, 0_f32)),
+						1_f32
+					);
+				}),
+			);
+		assert!(created.is_err());
+		assert!(client.scenegraph.get_node("/field/nan_sphere").is_none());
+	}
+
+	#[test]
+	pub(super) fn created_field_path_resolves_to_its_node() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "findable", Vec3::ZERO, 1_f32);
+		let path = method(&client, sphere.get_path(), "getPath", &[]).unwrap();
+		let path = flexbuffers::Reader::get_root(path.as_slice())
+			.unwrap()
+			.as_str()
+			.to_string();
+		assert_eq!(path, format!("{}/findable", FIELD_PARENT_PATH));
+		assert!(Arc::ptr_eq(
+			&client.scenegraph.get_node(&path).unwrap(),
+			&sphere
+		));
+	}
+
+	#[test]
+	pub(super) fn unsigned_distance_is_symmetric_about_the_surface() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "unsigned", Vec3::ZERO, 1_f32);
+		let inside = query_at(&client, &sphere, "unsigned_distance", Vec3::Y * 0.75_f32);
+		let outside = query_at(&client, &sphere, "unsigned_distance", Vec3::Y * 1.25_f32);
+		assert_near(inside, 0.25_f32, 0.0001_f32);
+		assert_near(outside, inside, 0.0001_f32);
+		assert!(distance_at(&client, &sphere, Vec3::Y * 0.75_f32) < 0_f32);
+	}
+
+	#[test]
+	pub(super) fn fields_containing_returns_exactly_the_overlapping_fields() {
+		let client = test_client();
+		for i in 0..3 {
+			create_sphere(&client, &format!("zone_{}", i), Vec3::X * i as f32, 0.8_f32);
+		}
+		let containing = |x: f32| -> Vec<String> {
+			let paths = method(
+				&client,
+				"/field",
+				"fieldsContaining",
+				&point_args(Vec3::X * x),
+			)
+			.unwrap();
+			let mut paths: Vec<String> = flexbuffers::Reader::get_root(paths.as_slice())
+				.unwrap()
+				.as_vector()
+				.iter()
+				.map(|path| path.as_str().to_string())
+				.collect();
+			paths.sort();
+			paths
+		};
+		assert_eq!(containing(0.5_f32), ["/field/zone_0", "/field/zone_1"]);
+		assert_eq!(containing(1_f32), ["/field/zone_1"]);
+		assert_eq!(containing(1.5_f32), ["/field/zone_1", "/field/zone_2"]);
+		assert!(containing(5_f32).is_empty());
+	}
+
+	#[test]
+	pub(super) fn swept_distance_finds_the_nearest_approach() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "passed_by", Vec3::ZERO, 1_f32);
+		let from = vec3(-3_f32, 2_f32, 0_f32);
+		let to = vec3(3_f32, 2_f32, 0_f32);
+		let swept = method(
+			&client,
+			sphere.get_path(),
+			"distance_swept",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				push_to_vec!(vec, mint::Vector3::from(from), mint::Vector3::from(to));
+			}),
+		)
+		.unwrap();
+		assert_near(read_f32(&swept), 1_f32, 0.001_f32);
+		assert!(distance_at(&client, &sphere, from) > 2.5_f32);
+		assert!(distance_at(&client, &sphere, to) > 2.5_f32);
+	}
+
+	#[test]
+	pub(super) fn missing_and_spatialless_parents_fail_differently() {
+		let client = test_client();
+		let create_under = |parent: &str| -> String {
+			let result = client
+				.scenegraph
+				.get_node("/field")
+				.unwrap()
+				.send_local_signal(
+					client.clone(),
+					"createSphereField",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push("orphan");
+						vec.push(parent);
+						push_to_vec!(vec, mint::Vector3::from(Vec3::ZERO), 1_f32);
+					}),
+				);
+			match result {
+				Err(ScenegraphError::SignalError { error }) => error.to_string(),
+				_ => panic!("created a field under {}", parent),
+			}
+		};
+		let missing = create_under("/nowhere");
+		// the field interface node exists but has no spatial
+		let spatialless = create_under("/field");
+		assert_ne!(missing, spatialless);
+		assert!(missing.contains("not found"), "{}", missing);
+		assert!(spatialless.contains("not a spatial"), "{}", spatialless);
+		assert!(client.scenegraph.get_node("/field/orphan").is_none());
+	}
+
+	#[test]
+	pub(super) fn equator_slice_of_a_sphere() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "sliced", Vec3::ZERO, 0.7_f32);
+		let slice = method(
+			&client,
+			sphere.get_path(),
+			"slice",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				push_to_vec!(
+					vec,
+					mint::Vector3::from(Vec3::ZERO),
+					mint::Vector3::from(Vec3::X),
+					mint::Vector3::from(Vec3::Z),
+					2_f32,
+					2_f32,
+					3_u32,
+					3_u32
+				);
+			}),
+		)
+		.unwrap();
+		let rows = flexbuffers::Reader::get_root(slice.as_slice())
+			.unwrap()
+			.as_vector();
+		assert_eq!(rows.len(), 3);
+		let sample = |x: usize, y: usize| rows.idx(y).as_vector().idx(x).as_f32();
+		assert_near(sample(1, 1), -0.7_f32, 0.0001_f32);
+		let corner = Vec2::splat(2_f32 / 3_f32).length() - 0.7_f32;
+		for (x, y) in [(0, 0), (2, 0), (0, 2), (2, 2)] {
+			assert_near(sample(x, y), corner, 0.0001_f32);
+		}
+	}
+
+	#[test]
+	pub(super) fn set_scale_doubles_world_distances() {
+		let client = test_client();
+		let box_node = create_box(&client, "scaled", Vec3::Y, Vec3::ONE);
+		let point = vec3(2_f32, 1_f32, 0.25_f32);
+		let before = distance_at(&client, &box_node, point);
+		assert_near(before, 1.5_f32, 0.0001_f32);
+
+		signal(
+			&client,
+			box_node.get_path(),
+			"setScale",
+			&flexbuffers::singleton(2_f32),
+		);
+		// the same point relative to the box, twice as far from its origin
+		let scaled_point = Vec3::Y + ((point - Vec3::Y) * 2_f32);
+		assert_near(
+			distance_at(&client, &box_node, scaled_point),
+			before * 2_f32,
+			0.0001_f32,
+		);
+		assert_near(distance_at(&client, &box_node, point), 1_f32, 0.0001_f32);
+
+		let rejected =
+			box_node.send_local_signal(client.clone(), "setScale", &flexbuffers::singleton(0_f32));
+		assert!(rejected.is_err());
+	}
+
+	#[test]
+	pub(super) fn list_methods_shows_each_shapes_own_signals() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "listed_sphere", Vec3::ZERO, 1_f32);
+		let box_node = create_box(&client, "listed_box", Vec3::ZERO, Vec3::ONE);
+		let list = |node: &Node| -> (Vec<String>, Vec<String>) {
+			let list = method(&client, node.get_path(), "listMethods", &[]).unwrap();
+			let list = flexbuffers::Reader::get_root(list.as_slice())
+				.unwrap()
+				.as_vector();
+			let names = |index: usize| {
+				list.idx(index)
+					.as_vector()
+					.iter()
+					.map(|name| name.as_str().to_string())
+					.collect::<Vec<_>>()
+			};
+			(names(0), names(1))
+		};
+		let (sphere_methods, sphere_signals) = list(&sphere);
+		let (box_methods, box_signals) = list(&box_node);
+		for methods in [&sphere_methods, &box_methods] {
+			for name in ["distance", "normal", "closest_point", "listMethods"] {
+				assert!(
+					methods.iter().any(|method| method == name),
+					"{} missing",
+					name
+				);
+			}
+		}
+		assert!(sphere_signals.iter().any(|signal| signal == "setRadius"));
+		assert!(!sphere_signals.iter().any(|signal| signal == "setSize"));
+		assert!(box_signals.iter().any(|signal| signal == "setSize"));
+		assert!(!box_signals.iter().any(|signal| signal == "setRadius"));
+	}
+
+	#[test]
+	pub(super) fn fields_containing_order_is_stable() {
+		let client = test_client();
+		// created out of order so insertion order can't pass for sorted
+		for name in ["m", "c", "x", "a", "q", "h"] {
+			create_sphere(&client, name, Vec3::ZERO, 1_f32);
+		}
+		let query = || {
+			method(
+				&client,
+				"/field",
+				"fieldsContaining",
+				&point_args(Vec3::ZERO),
+			)
+			.unwrap()
+		};
+		let first = query();
+		for _ in 0..20 {
+			assert_eq!(query(), first);
+		}
+		let paths: Vec<String> = flexbuffers::Reader::get_root(first.as_slice())
+			.unwrap()
+			.as_vector()
+			.iter()
+			.map(|path| path.as_str().to_string())
+			.collect();
+		assert_eq!(paths.len(), 6);
+		assert!(paths.windows(2).all(|pair| pair[0] < pair[1]));
+	}
+
+	#[test]
+	pub(super) fn field_as_its_own_reference_space_gives_local_distance() {
+		let client = test_client();
+		let box_node = create_box(
+			&client,
+			"self_referenced",
+			vec3(3_f32, -1_f32, 2_f32),
+			vec3(1_f32, 2_f32, 0.5_f32),
+		);
+		box_node
+			.spatial
+			.get()
+			.unwrap()
+			.set_local_transform(Mat4::from_scale_rotation_translation(
+				Vec3::splat(1.5_f32),
+				Quat::from_rotation_y(0.7_f32),
+				vec3(3_f32, -1_f32, 2_f32),
+			));
+		let field = box_node.field.get().unwrap();
+		let path = box_node.get_path();
+		for point in [
+			Vec3::ZERO,
+			vec3(1_f32, 0.2_f32, 0_f32),
+			vec3(-0.3_f32, 2_f32, 0.6_f32),
+		] {
+			let distance = method(
+				&client,
+				path,
+				"distance",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push(path);
+					push_to_vec!(vec, mint::Vector3::from(point));
+				}),
+			)
+			.unwrap();
+			assert_eq!(read_f32(&distance), field.local_distance(point.into()));
+		}
+	}
+
+	#[test]
+	pub(super) fn distances_from_origins_match_single_queries() {
+		let client = test_client();
+		crate::nodes::spatial::create_interface(&client);
+		let sphere = create_sphere(&client, "fingertip_target", Vec3::ZERO, 1_f32);
+		let origins = [
+			vec3(0_f32, 0_f32, 2_f32),
+			vec3(3_f32, 0_f32, 0_f32),
+			vec3(0_f32, 0.5_f32, 0_f32),
+		];
+		for (i, origin) in origins.iter().enumerate() {
+			signal(
+				&client,
+				"/spatial",
+				"createSpatial",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push(format!("fingertip{}", i).as_str());
+					vec.push("/");
+					push_to_vec!(
+						vec,
+						mint::Vector3::from(*origin),
+						mint::Quaternion::from(Quat::IDENTITY),
+						mint::Vector3::from(Vec3::ONE)
+					);
+				}),
+			);
+		}
+		let distances = method(
+			&client,
+			sphere.get_path(),
+			"distances_from_origins",
+			&flexbuffer_from_vector_arguments(|vec| {
+				for i in 0..origins.len() {
+					vec.push(format!("/spatial/spatial/fingertip{}", i).as_str());
+				}
+			}),
+		)
+		.unwrap();
+		let distances = flexbuffers::Reader::get_root(distances.as_slice())
+			.unwrap()
+			.as_vector();
+		assert_eq!(distances.len(), origins.len());
+		for (distance, origin) in distances.iter().zip(origins) {
+			assert_near(
+				distance.as_f32(),
+				distance_at(&client, &sphere, origin),
+				0.0001_f32,
+			);
+		}
+		assert_near(distances.idx(0).as_f32(), 1_f32, 0.0001_f32);
+		assert_near(distances.idx(1).as_f32(), 2_f32, 0.0001_f32);
+		assert_near(distances.idx(2).as_f32(), -0.5_f32, 0.0001_f32);
+	}
+
+	#[test]
+	pub(super) fn get_matrix_transforms_like_space_to_space_matrix() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "transformed", Vec3::ZERO, 1_f32);
+		let field_transform = Mat4::from_scale_rotation_translation(
+			vec3(2_f32, 0.5_f32, 1_f32),
+			Quat::from_rotation_y(0.7_f32),
+			vec3(1_f32, 2_f32, 3_f32),
+		);
+		sphere
+			.spatial
+			.get()
+			.unwrap()
+			.set_local_transform(field_transform);
+
+		let raw = method(
+			&client,
+			sphere.get_path(),
+			"getMatrix",
+			&flexbuffers::singleton("/"),
+		)
+		.unwrap();
+		let raw = flexbuffers::Reader::get_root(raw.as_slice())
+			.unwrap()
+			.as_vector();
+		assert_eq!(raw.len(), 16);
+		let mut cols = [0_f32; 16];
+		for (value, flex) in cols.iter_mut().zip(raw.iter()) {
+			*value = flex.as_f32();
+		}
+		let matrix = Mat4::from_cols_array(&cols);
+
+		let root = get_reference_space_flex(&client, "/").unwrap();
+		let expected = Spatial::space_to_space_matrix(
+			Some(root.as_ref()),
+			Some(sphere.field.get().unwrap().spatial_ref()),
+		);
+		for point in [Vec3A::ZERO, Vec3A::X, vec3a(-2_f32, 0.5_f32, 4_f32)] {
+			assert!(matrix
+				.transform_point3a(point)
+				.abs_diff_eq(expected.transform_point3a(point), 0.0001_f32));
+		}
+		assert!(matrix
+			.transform_point3a(vec3a(1_f32, 2_f32, 3_f32))
+			.abs_diff_eq(Vec3A::ZERO, 0.0001_f32));
+	}
+
+	#[test]
+	pub(super) fn proximity_fades_over_the_bounding_radius() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "fading", Vec3::ZERO, 1_f32);
+		let proximity = |point: Vec3| {
+			read_f32(&method(&client, sphere.get_path(), "proximity", &point_args(point)).unwrap())
+		};
+		assert_near(proximity(Vec3::ZERO), 1_f32, 0.0001_f32);
+		assert_near(proximity(Vec3::X), 1_f32, 0.0001_f32);
+		// half the bounding radius away from the surface
+		assert_near(proximity(Vec3::X * 1.5_f32), 0.5_f32, 0.0001_f32);
+		assert_near(proximity(Vec3::X * 10_f32), 0_f32, 0.0001_f32);
+	}
+
+	#[test]
+	pub(super) fn principal_axes_of_an_elongated_box() {
+		let client = test_client();
+		let position = vec3(1_f32, 2_f32, 3_f32);
+		let box_node = create_box(&client, "plank", position, vec3(4_f32, 0.5_f32, 1_f32));
+		let axes = method(
+			&client,
+			box_node.get_path(),
+			"principal_axes",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				vec.push(8192_u32);
+			}),
+		)
+		.unwrap();
+		let (centroid, major, middle, minor) = match read_vec3s(&axes)[..] {
+			[centroid, major, middle, minor] => (centroid, major, middle, minor),
+			_ => panic!("Expected a centroid and three axes"),
+		};
+		assert!(centroid.abs_diff_eq(position, 0.1_f32), "{}", centroid);
+		assert!(major.dot(Vec3::X).abs() > 0.99_f32, "{}", major);
+		assert!(middle.dot(Vec3::Z).abs() > 0.99_f32, "{}", middle);
+		assert!(minor.dot(Vec3::Y).abs() > 0.99_f32, "{}", minor);
+	}
+
+	#[test]
+	pub(super) fn queries_report_their_distance_evaluations() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "analytic", Vec3::ZERO, 1_f32);
+		let box_node = create_box(&client, "differenced", Vec3::ZERO, Vec3::ONE);
+		let evaluations = |node: &Node, query: &str| {
+			let report = method(
+				&client,
+				node.get_path(),
+				query,
+				&query_args(vec3(2_f32, 0.3_f32, 0.1_f32), |options| {
+					options.push("evaluations", true)
+				}),
+			)
+			.unwrap();
+			flexbuffers::Reader::get_root(report.as_slice())
+				.unwrap()
+				.as_map()
+				.idx("evaluations")
+				.as_u32()
+		};
+		assert_eq!(evaluations(&sphere, "distance"), 1);
+		assert_eq!(evaluations(&sphere, "normal"), 0);
+		// the distance itself plus one offset along each axis
+		assert_eq!(evaluations(&box_node, "normal"), 4);
+	}
+
+	#[test]
+	pub(super) fn sampling_is_deterministic_per_seed() {
+		let client = test_client();
+		let box_node = create_box(&client, "sampled", Vec3::ZERO, vec3(2_f32, 1_f32, 0.5_f32));
+		let sample = |query: &str, count: u32, seed: Option<u64>| {
+			read_vec3s(
+				&method(
+					&client,
+					box_node.get_path(),
+					query,
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push("/");
+						vec.push(count);
+						if let Some(seed) = seed {
+							vec.push(seed);
+						}
+					}),
+				)
+				.unwrap(),
+			)
+		};
+		for (query, count) in [("sample_surface", 32), ("principal_axes", 256)] {
+			assert_eq!(sample(query, count, Some(7)), sample(query, count, Some(7)));
+			assert_ne!(sample(query, count, Some(7)), sample(query, count, Some(8)));
+			// no seed still gives the same result every time
+			assert_eq!(sample(query, count, None), sample(query, count, None));
+		}
+	}
+
+	#[test]
+	pub(super) fn quantized_distances_stay_within_a_step() {
+		let client = test_client();
+		crate::nodes::spatial::create_interface(&client);
+		let sphere = create_sphere(&client, "quantized", Vec3::ZERO, 1_f32);
+		let origins = [
+			vec3(0_f32, 0_f32, 1.23456_f32),
+			vec3(2.5_f32, 0.1_f32, 0_f32),
+			vec3(0_f32, 0.3337_f32, 0_f32),
+			vec3(40_f32, 0_f32, 0_f32),
+		];
+		let paths: Vec<String> = origins
+			.iter()
+			.enumerate()
+			.map(|(i, origin)| {
+				signal(
+					&client,
+					"/spatial",
+					"createSpatial",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push(format!("probe{}", i).as_str());
+						vec.push("/");
+						push_to_vec!(
+							vec,
+							mint::Vector3::from(*origin),
+							mint::Quaternion::from(Quat::IDENTITY),
+							mint::Vector3::from(Vec3::ONE)
+						);
+					}),
+				);
+				format!("/spatial/spatial/probe{}", i)
+			})
+			.collect();
+		let step = 0.001_f32;
+		let quantized = method(
+			&client,
+			sphere.get_path(),
+			"quantized_distances_from_origins",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push(step);
+				let mut spaces = vec.start_vector();
+				for path in &paths {
+					spaces.push(path.as_str());
+				}
+				spaces.end_vector();
+			}),
+		)
+		.unwrap();
+		let quantized = flexbuffers::Reader::get_root(quantized.as_slice())
+			.unwrap()
+			.as_map();
+		assert_eq!(quantized.idx("step").as_f32(), step);
+		let max = quantized.idx("max").as_f32();
+		assert_near(quantized.idx("min").as_f32(), -max, 0_f32);
+		let distances = quantized.idx("distances").as_vector();
+		assert_eq!(distances.len(), origins.len());
+		for (distance, origin) in distances.iter().zip(origins) {
+			let exact = origin.length() - 1_f32;
+			let dequantized = distance.as_i16() as f32 * step;
+			if exact < max {
+				assert!(
+					(dequantized - exact).abs() <= step,
+					"{} vs {}",
+					dequantized,
+					exact
+				);
+			} else {
+				assert_near(dequantized, max, step);
+			}
+		}
+	}
+
+	#[test]
+	pub(super) fn blob_distances_match_single_queries() {
+		let client = test_client();
+		let box_node = create_box(
+			&client,
+			"cloud_target",
+			vec3(0.2_f32, 0_f32, -0.3_f32),
+			vec3(1_f32, 0.5_f32, 2_f32),
+		);
+		let mut state = 1_u64;
+		let points: Vec<Vec3> = (0..1000)
+			.map(|_| {
+				vec3(
+					next_random(&mut state),
+					next_random(&mut state),
+					next_random(&mut state),
+				) * 4_f32 - Vec3::splat(2_f32)
+			})
+			.collect();
+		let blob: Vec<u8> = points
+			.iter()
+			.flat_map(|point| point.to_array())
+			.flat_map(|component| component.to_le_bytes())
+			.collect();
+		let blob_args = |count: u32| {
+			flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				vec.push(flexbuffers::Blob(blob.as_slice()));
+				vec.push(count);
+			})
+		};
+
+		let distances = method(
+			&client,
+			box_node.get_path(),
+			"distances_blob",
+			&blob_args(1000),
+		)
+		.unwrap();
+		let distances = flexbuffers::Reader::get_root(distances.as_slice())
+			.unwrap()
+			.get_blob()
+			.unwrap()
+			.0;
+		assert_eq!(distances.len(), points.len() * 4);
+		for (distance, point) in distances.chunks_exact(4).zip(points) {
+			let distance = f32::from_le_bytes([distance[0], distance[1], distance[2], distance[3]]);
+			assert_near(
+				distance,
+				distance_at(&client, &box_node, point),
+				0.00001_f32,
+			);
+		}
+		assert!(method(
+			&client,
+			box_node.get_path(),
+			"distances_blob",
+			&blob_args(999)
+		)
+		.is_err());
+	}
+
+	#[test]
+	pub(super) fn local_points_to_space_matches_single_transforms() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "sampled_local", Vec3::ZERO, 1_f32);
+		sphere
+			.spatial
+			.get()
+			.unwrap()
+			.set_local_transform(Mat4::from_scale_rotation_translation(
+				Vec3::splat(2_f32),
+				Quat::from_rotation_z(std::f32::consts::FRAC_PI_2),
+				vec3(0_f32, 1_f32, 0_f32),
+			));
+		let local_points = [Vec3::ZERO, Vec3::X, vec3(0.5_f32, -1_f32, 2_f32)];
+		let points = method(
+			&client,
+			sphere.get_path(),
+			"local_points_to_space",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				let mut points = vec.start_vector();
+				for point in local_points {
+					push_to_vec!(&mut points, mint::Vector3::from(point));
+				}
+				points.end_vector();
+			}),
+		)
+		.unwrap();
+		let points = read_vec3s(&points);
+
+		let root = get_reference_space_flex(&client, "/").unwrap();
+		let local_to_root = Spatial::space_to_space_matrix(
+			Some(sphere.field.get().unwrap().spatial_ref()),
+			Some(root.as_ref()),
+		);
+		assert_eq!(points.len(), local_points.len());
+		for (point, local_point) in points.iter().zip(local_points) {
+			assert!(point.abs_diff_eq(local_to_root.transform_point3(local_point), 0.0001_f32));
+		}
+		assert!(points[1].abs_diff_eq(vec3(0_f32, 3_f32, 0_f32), 0.0001_f32));
+	}
+
+	#[test]
+	pub(super) fn box_corners_distance_reports_the_nearest_corner() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "panel_neighbour", Vec3::ZERO, 1_f32);
+		let corners_distance = |min: Vec3, max: Vec3| {
+			read_f32(
+				&method(
+					&client,
+					sphere.get_path(),
+					"box_corners_distance",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push("/");
+						push_to_vec!(vec, mint::Vector3::from(min), mint::Vector3::from(max));
+					}),
+				)
+				.unwrap(),
+			)
+		};
+		let (min, max) = (
+			vec3(1.5_f32, -0.5_f32, -0.5_f32),
+			vec3(2.5_f32, 0.5_f32, 0.5_f32),
+		);
+		let nearest_corner = vec3(1.5_f32, 0.5_f32, 0.5_f32);
+		assert_near(
+			corners_distance(min, max),
+			nearest_corner.length() - 1_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			corners_distance(min, max),
+			distance_at(&client, &sphere, nearest_corner),
+			0.0001_f32,
+		);
+		// moving the far side of the box away doesn't change the nearest corner
+		assert_near(
+			corners_distance(min, max + Vec3::X * 5_f32),
+			nearest_corner.length() - 1_f32,
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	pub(super) fn path_crossings_bracket_the_inside_of_a_sphere() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "threaded", Vec3::ZERO, 1_f32);
+		let crossings = method(
+			&client,
+			sphere.get_path(),
+			"path_crossings",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				let mut points = vec.start_vector();
+				for point in [-Vec3::X * 2_f32, Vec3::ZERO, Vec3::Z * 3_f32] {
+					push_to_vec!(&mut points, mint::Vector3::from(point));
+				}
+				points.end_vector();
+			}),
+		)
+		.unwrap();
+		let crossings = flexbuffers::Reader::get_root(crossings.as_slice())
+			.unwrap()
+			.as_vector();
+		assert_eq!(crossings.len(), 4);
+		// halfway along the first segment, then a third of the way along the second
+		assert_near(crossings.idx(0).as_f32(), 0.5_f32, 0.001_f32);
+		assert!(crossings.idx(1).as_bool());
+		assert_near(crossings.idx(2).as_f32(), 4_f32 / 3_f32, 0.001_f32);
+		assert!(!crossings.idx(3).as_bool());
+	}
+
+	#[test]
+	pub(super) fn ambient_occlusion_is_higher_in_a_notch() {
+		let client = test_client();
+		// a floor with two blocks on it leaving a 0.2 wide notch between them
+		let floor = create_box(
+			&client,
+			"notch_floor",
+			-Vec3::Y * 0.5_f32,
+			vec3(3_f32, 1_f32, 2_f32),
+		);
+		let left = create_box(
+			&client,
+			"notch_left",
+			vec3(-0.6_f32, 0.5_f32, 0_f32),
+			vec3(1_f32, 1_f32, 2_f32),
+		);
+		let right = create_box(
+			&client,
+			"notch_right",
+			vec3(0.6_f32, 0.5_f32, 0_f32),
+			vec3(1_f32, 1_f32, 2_f32),
+		);
+		let notched = create_field(&client, "createUnionField", "notched", Vec3::ZERO, |vec| {
+			let mut children = vec.start_vector();
+			for child in [&floor, &left, &right] {
+				children.push(child.get_path());
+			}
+			children.end_vector();
+			vec.push(0_f32);
+		});
+		let sphere = create_sphere(&client, "convex", Vec3::X * 10_f32, 1_f32);
+		let occlusion = |node: &Node, point: Vec3| {
+			read_f32(
+				&method(
+					&client,
+					node.get_path(),
+					"ambient_occlusion",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push("/");
+						push_to_vec!(vec, mint::Vector3::from(point));
+						vec.push(0.1_f32);
+					}),
+				)
+				.unwrap(),
+			)
+		};
+		let in_notch = occlusion(&notched, vec3(0_f32, 0.01_f32, 0_f32));
+		let on_sphere = occlusion(&sphere, vec3(10_f32, 1.01_f32, 0_f32));
+		assert!(
+			in_notch > on_sphere + 0.25_f32,
+			"{} vs {}",
+			in_notch,
+			on_sphere
+		);
+		assert!(on_sphere < 0.05_f32);
+		assert!(in_notch <= 1_f32);
+	}
+
+	#[test]
+	pub(super) fn distance_from_matrix_matches_the_node_path() {
+		let client = test_client();
+		let box_node = create_box(
+			&client,
+			"matrix_target",
+			vec3(1_f32, 2_f32, 3_f32),
+			vec3(1_f32, 2_f32, 0.5_f32),
+		);
+		box_node
+			.spatial
+			.get()
+			.unwrap()
+			.set_local_transform(Mat4::from_rotation_translation(
+				Quat::from_rotation_x(0.4_f32),
+				vec3(1_f32, 2_f32, 3_f32),
+			));
+		let distance_from_matrix = |matrix: Mat4, point: Vec3| {
+			read_f32(
+				&method(
+					&client,
+					box_node.get_path(),
+					"distance_from_matrix",
+					&flexbuffer_from_vector_arguments(|vec| {
+						let mut columns = vec.start_vector();
+						for value in matrix.to_cols_array() {
+							columns.push(value);
+						}
+						columns.end_vector();
+						push_to_vec!(vec, mint::Vector3::from(point));
+					}),
+				)
+				.unwrap(),
+			)
+		};
+		let point = vec3(0.2_f32, 3.5_f32, 2_f32);
+
+		// identity means the point is already in the field's space
+		let local_point = Spatial::space_to_space_matrix(
+			Some(get_reference_space_flex(&client, "/").unwrap().as_ref()),
+			Some(box_node.field.get().unwrap().spatial_ref()),
+		)
+		.transform_point3(point);
+		assert_near(
+			distance_from_matrix(Mat4::IDENTITY, local_point),
+			distance_at(&client, &box_node, point),
+			0.0001_f32,
+		);
+
+		let raw = method(
+			&client,
+			box_node.get_path(),
+			"getMatrix",
+			&flexbuffers::singleton("/"),
+		)
+		.unwrap();
+		let raw = flexbuffers::Reader::get_root(raw.as_slice())
+			.unwrap()
+			.as_vector();
+		let mut columns = [0_f32; 16];
+		for (value, flex) in columns.iter_mut().zip(raw.iter()) {
+			*value = flex.as_f32();
+		}
+		assert_near(
+			distance_from_matrix(Mat4::from_cols_array(&columns), point),
+			distance_at(&client, &box_node, point),
+			0.0001_f32,
+		);
+
+		let mut too_short = flexbuffers::Builder::default();
+		let mut args = too_short.start_vector();
+		let mut columns = args.start_vector();
+		columns.push(1_f32);
+		columns.end_vector();
+		push_to_vec!(&mut args, mint::Vector3::from(point));
+		args.end_vector();
+		assert!(method(
+			&client,
+			box_node.get_path(),
+			"distance_from_matrix",
+			too_short.view()
+		)
+		.is_err());
+	}
+
+	#[test]
+	pub(super) fn field_distances_match_per_field_queries() {
+		let client = test_client();
+		crate::nodes::spatial::create_interface(&client);
+		signal(
+			&client,
+			"/spatial",
+			"createSpatial",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("hand");
+				vec.push("/");
+				push_to_vec!(
+					vec,
+					mint::Vector3::from(vec3(0.5_f32, -1_f32, 2_f32)),
+					mint::Quaternion::from(Quat::from_rotation_y(0.3_f32)),
+					mint::Vector3::from(Vec3::splat(2_f32))
+				);
+			}),
+		);
+		let fields: Vec<_> = (0..10)
+			.map(|i| {
+				let position = vec3(i as f32, (i % 3) as f32, -(i as f32) * 0.5_f32);
+				if i % 2 == 0 {
+					create_sphere(&client, &format!("grabbable{}", i), position, 0.5_f32)
+				} else {
+					create_box(
+						&client,
+						&format!("grabbable{}", i),
+						position,
+						Vec3::splat(0.7_f32),
+					)
+				}
+			})
+			.collect();
+		let point = vec3(0.2_f32, 0.4_f32, -0.1_f32);
+		let distances = method(
+			&client,
+			"/field",
+			"fieldDistances",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/spatial/spatial/hand");
+				push_to_vec!(vec, mint::Vector3::from(point));
+				let mut paths = vec.start_vector();
+				for field in &fields {
+					paths.push(field.get_path());
+				}
+				paths.end_vector();
+			}),
+		)
+		.unwrap();
+		let distances = flexbuffers::Reader::get_root(distances.as_slice())
+			.unwrap()
+			.as_vector();
+		assert_eq!(distances.len(), fields.len());
+		for (distance, field) in distances.iter().zip(&fields) {
+			let single = read_f32(
+				&method(
+					&client,
+					field.get_path(),
+					"distance",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push("/spatial/spatial/hand");
+						push_to_vec!(vec, mint::Vector3::from(point));
+					}),
+				)
+				.unwrap(),
+			);
+			assert_near(distance.as_f32(), single, 0.0001_f32);
+		}
+	}
+
+	#[test]
+	pub(super) fn directional_derivative_on_a_sphere() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "slope", vec3(1_f32, 0_f32, 0_f32), 1_f32);
+		let derivative = |point: Vec3, direction: Vec3| {
+			read_f32(
+				&method(
+					&client,
+					sphere.get_path(),
+					"directional_derivative",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push("/");
+						push_to_vec!(
+							vec,
+							mint::Vector3::from(point),
+							mint::Vector3::from(direction)
+						);
+					}),
+				)
+				.unwrap(),
+			)
+		};
+		let point = vec3(1_f32, 2_f32, 0_f32);
+		assert_near(derivative(point, Vec3::Y), 1_f32, 0.001_f32);
+		assert_near(derivative(point, -Vec3::Y * 3_f32), -1_f32, 0.001_f32);
+		assert_near(derivative(point, Vec3::X), 0_f32, 0.001_f32);
+		assert_near(derivative(point, Vec3::Z), 0_f32, 0.001_f32);
+		assert_near(
+			derivative(point, vec3(0_f32, 1_f32, 1_f32)),
+			0.5_f32.sqrt(),
+			0.001_f32,
+		);
+	}
+
+	#[test]
+	pub(super) fn distance_in_ancestor_spaces() {
+		let client = test_client();
+		crate::nodes::spatial::create_interface(&client);
+		signal(
+			&client,
+			"/spatial",
+			"createSpatial",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("arm");
+				vec.push("/");
+				push_to_vec!(
+					vec,
+					mint::Vector3::from(Vec3::Y),
+					mint::Quaternion::from(Quat::IDENTITY),
+					mint::Vector3::from(Vec3::ONE)
+				);
+			}),
+		);
+		signal(
+			&client,
+			"/field",
+			"createSphereField",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("hand");
+				vec.push("/spatial/spatial/arm");
+				push_to_vec!(vec, mint::Vector3::from(Vec3::X * 2_f32));
+				vec.push(0.5_f32);
+			}),
+		);
+		let distance_in_ancestor = |depth: u32, point: Vec3| {
+			method(
+				&client,
+				"/field/hand",
+				"distance_in_ancestor",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push(depth);
+					push_to_vec!(vec, mint::Vector3::from(point));
+				}),
+			)
+			.map(|distance| read_f32(&distance))
+		};
+		// the same point means somewhere different in each space
+		assert_near(
+			distance_in_ancestor(1, Vec3::ZERO).unwrap(),
+			1.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_in_ancestor(1, Vec3::X * 2_f32).unwrap(),
+			-0.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_in_ancestor(2, vec3(2_f32, 1_f32, 0_f32)).unwrap(),
+			-0.5_f32,
+			0.0001_f32,
+		);
+		assert!(distance_in_ancestor(3, Vec3::ZERO).is_err());
+		// the field's own space isn't an ancestor
+		assert!(distance_in_ancestor(0, Vec3::ZERO).is_err());
+	}
+
+	#[test]
+	pub(super) fn snap_step_never_overshoots() {
+		let client = test_client();
+		let box_node = create_box(
+			&client,
+			"snap_target",
+			Vec3::ZERO,
+			vec3(1_f32, 0.5_f32, 2_f32),
+		);
+		let mut state = 3_u64;
+		for i in 0..200 {
+			// from just inside out to well past the bounding radius
+			let reach = 0.05_f32 + (i as f32 * 0.05_f32);
+			let point = Vec3::from(random_direction(&mut state)) * reach;
+			let snap = method(
+				&client,
+				box_node.get_path(),
+				"snap_step",
+				&point_args(point),
+			)
+			.unwrap();
+			let snap = flexbuffers::Reader::get_root(snap.as_slice())
+				.unwrap()
+				.as_vector();
+			let closest_point: Vec3 = flex_to_vec3!(snap.idx(0)).unwrap().into();
+			let step = snap.idx(1).as_f32();
+
+			let distance = distance_at(&client, &box_node, point);
+			assert!(step >= 0_f32 && step <= point.distance(closest_point) + 0.0001_f32);
+			let stepped = point + ((closest_point - point).normalize_or_zero() * step);
+			let stepped_distance = distance_at(&client, &box_node, stepped);
+			assert!(
+				stepped_distance * distance.signum() >= -0.0001_f32,
+				"{} stepped {} from {} to {}",
+				point,
+				step,
+				distance,
+				stepped_distance
+			);
+		}
+	}
+
+	#[test]
+	pub(super) fn metrics_count_fields_and_queries() {
+		let client = test_client();
+		let metrics = || {
+			let metrics = method(
+				&client,
+				"/field",
+				"getMetrics",
+				&flexbuffers::singleton(false),
+			)
+			.unwrap();
+			let metrics = flexbuffers::Reader::get_root(metrics.as_slice())
+				.unwrap()
+				.as_vector();
+			(
+				metrics.idx(0).as_u64(),
+				metrics.idx(1).as_u64(),
+				metrics.idx(2).as_f64(),
+				metrics.idx(3).as_f64(),
+			)
+		};
+		let (_, queries_before, _, _) = metrics();
+		let fields: Vec<_> = (0..3)
+			.map(|i| {
+				create_sphere(
+					&client,
+					&format!("metered{}", i),
+					Vec3::X * i as f32,
+					0.5_f32,
+				)
+			})
+			.collect();
+		for field in &fields {
+			for _ in 0..5 {
+				distance_at(&client, field, Vec3::Y);
+			}
+		}
+		// other tests share the server wide counters, so only lower bounds hold
+		let (field_count, queries, queries_per_second, average_latency) = metrics();
+		assert!(field_count >= fields.len() as u64);
+		assert!(queries >= queries_before + 15);
+		assert!(queries_per_second > 0_f64);
+		assert!(average_latency > 0_f64);
+	}
+
+	pub(super) struct CaptureLogger(Mutex<Vec<(log::Level, String)>>);
+	impl log::Log for CaptureLogger {
+		fn enabled(&self, _metadata: &log::Metadata) -> bool {
+			true
+		}
+		fn log(&self, record: &log::Record) {
+			self.0
+				.lock()
+				.push((record.level(), record.args().to_string()));
+		}
+		fn flush(&self) {}
+	}
+	pub(super) static LOGGER: CaptureLogger = CaptureLogger(parking_lot::const_mutex(Vec::new()));
+
+	#[test]
+	pub(super) fn failed_field_queries_and_signals_warn_with_context() {
+		// only this test installs a logger, the others just log into it
+		let _ = log::set_logger(&LOGGER);
+		log::set_max_level(log::LevelFilter::Warn);
+
+		let client = test_client();
+		let sphere = create_sphere(&client, "logged", Vec3::ZERO, 1_f32);
+		let result = method(
+			&client,
+			sphere.get_path(),
+			"distance",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/nowhere");
+				push_to_vec!(vec, mint::Vector3::from(Vec3::ZERO));
+			}),
+		);
+		assert!(matches!(result, Err(ScenegraphError::MethodError { .. })));
+		assert!(sphere
+			.send_local_signal(client.clone(), "setRadius", &flexbuffers::singleton(-1_f32))
+			.is_err());
+
+		let logs = LOGGER.0.lock();
+		let client_logs: Vec<_> = logs
+			.iter()
+			.filter(|(_, message)| message.contains(&client.uid))
+			.collect();
+		assert_eq!(client_logs.len(), 2);
+		let (level, message) = client_logs[0];
+		assert_eq!(*level, log::Level::Warn);
+		assert!(message.contains("method distance"));
+		assert!(message.contains("/field/logged"));
+		assert!(message.contains("Reference space node does not exist"));
+		assert!(client_logs[1].1.contains("signal setRadius"));
+	}
+
+	#[test]
+	pub(super) fn plane_closest_point_on_a_sphere_equator() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "planet", Vec3::ZERO, 1_f32);
+		let plane_closest_point = |point: Vec3, plane_origin: Vec3| {
+			read_vec3s(
+				&method(
+					&client,
+					sphere.get_path(),
+					"plane_closest_point",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push("/");
+						push_to_vec!(
+							vec,
+							mint::Vector3::from(point),
+							mint::Vector3::from(plane_origin),
+							mint::Vector3::from(Vec3::Y)
+						);
+					}),
+				)
+				.unwrap(),
+			)
+		};
+		let found = plane_closest_point(vec3(2_f32, 1_f32, 1_f32), Vec3::ZERO);
+		assert_eq!(found.len(), 1);
+		let point = found[0];
+		assert_near(point.y, 0_f32, 0.0001_f32);
+		assert_near(distance_at(&client, &sphere, point), 0_f32, 0.001_f32);
+		assert!(
+			point.abs_diff_eq(vec3(2_f32, 0_f32, 1_f32).normalize(), 0.001_f32),
+			"{}",
+			point
+		);
+		// a plane above the sphere never meets it
+		assert!(plane_closest_point(vec3(2_f32, 1_f32, 1_f32), Vec3::Y * 2_f32).is_empty());
+	}
+
+	#[test]
+	pub(super) fn plane_closest_point_is_exact_on_scaled_cached_fields() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "scaled_planet", Vec3::ZERO, 1_f32);
+		let path = sphere.get_path();
+		signal(&client, path, "setScale", &flexbuffers::singleton(2_f32));
+		signal(
+			&client,
+			path,
+			"setCacheEnabled",
+			&flexbuffers::singleton(true),
+		);
+		signal(
+			&client,
+			path,
+			"setLodThreshold",
+			&flexbuffers::singleton(0.5_f32),
+		);
+		let found = read_vec3s(
+			&method(
+				&client,
+				path,
+				"plane_closest_point",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push("/");
+					push_to_vec!(
+						vec,
+						mint::Vector3::from(vec3(4_f32, 1_f32, 2_f32)),
+						mint::Vector3::from(Vec3::ZERO),
+						mint::Vector3::from(Vec3::Y)
+					);
+				}),
+			)
+			.unwrap(),
+		);
+		assert_eq!(found.len(), 1);
+		let expected = vec3(4_f32, 0_f32, 2_f32).normalize() * 2_f32;
+		assert!(found[0].abs_diff_eq(expected, 0.001_f32), "{}", found[0]);
+	}
+
+	#[test]
+	pub(super) fn chunked_grid_bake_reassembles_to_a_single_bake() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "baked", Vec3::ZERO, 0.6_f32);
+		let (min, max) = (-Vec3::ONE, Vec3::ONE);
+		// two layers fill a chunk at this resolution, so three layers take two chunks
+		let resolution = uvec3(128, 256, 3);
+		let bake_chunk = |chunk: u32| {
+			method(
+				&client,
+				sphere.get_path(),
+				"bake_grid_chunk",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push("/");
+					push_to_vec!(vec, mint::Vector3::from(min), mint::Vector3::from(max));
+					vec.push(resolution.x);
+					vec.push(resolution.y);
+					vec.push(resolution.z);
+					vec.push(chunk);
+				}),
+			)
+		};
+
+		let mut reassembled = vec![f32::NAN; (resolution.x * resolution.y * resolution.z) as usize];
+		let layer_cells = (resolution.x * resolution.y) as usize;
+		// out of order on purpose, each chunk says where it goes
+		for chunk in [1, 0] {
+			let baked = bake_chunk(chunk).unwrap();
+			let baked = flexbuffers::Reader::get_root(baked.as_slice())
+				.unwrap()
+				.as_vector();
+			let dimensions = baked.idx(0).as_vector();
+			assert_eq!(dimensions.idx(2).as_u32(), resolution.z);
+			assert_eq!(baked.idx(3).as_u32(), 2);
+			let start = baked.idx(4).as_u32() as usize * layer_cells;
+			for (i, distance) in baked.idx(5).as_vector().iter().enumerate() {
+				reassembled[start + i] = distance.as_f32();
+			}
+		}
+		assert!(bake_chunk(2).is_err());
+
+		let single = sphere.field.get().unwrap().bake_grid_layers(
+			get_reference_space_flex(&client, "/").unwrap().as_ref(),
+			min.into(),
+			max.into(),
+			resolution,
+			0..resolution.z,
+		);
+		assert_eq!(reassembled, single);
+		// X varies fastest, then Y, then Z
+		let cell_center = |x: u32, y: u32, z: u32| {
+			min + ((vec3(x as f32, y as f32, z as f32) + Vec3::splat(0.5_f32))
+				/ resolution.as_vec3())
+				* (max - min)
+		};
+		for (x, y, z) in [(0, 0, 0), (64, 200, 1), (127, 255, 2)] {
+			let index = (x + (y * resolution.x) + (z * resolution.x * resolution.y)) as usize;
+			assert_near(
+				reassembled[index],
+				distance_at(&client, &sphere, cell_center(x, y, z)),
+				0.0001_f32,
+			);
+		}
+	}
+
+	#[test]
+	pub(super) fn contained_in_a_big_box() {
+		let client = test_client();
+		let big_box = create_box(&client, "big_zone", Vec3::ZERO, Vec3::splat(4_f32));
+		let inside = create_sphere(
+			&client,
+			"small_zone",
+			vec3(0.5_f32, -0.5_f32, 1_f32),
+			0.5_f32,
+		);
+		let straddling = create_sphere(&client, "straddling_zone", Vec3::X * 2_f32, 0.5_f32);
+		let contained_in = |node: &Node, other: &Node| {
+			let contained = method(
+				&client,
+				node.get_path(),
+				"contained_in",
+				&flexbuffer_from_vector_arguments(|vec| vec.push(other.get_path())),
+			)
+			.unwrap();
+			flexbuffers::Reader::get_root(contained.as_slice())
+				.unwrap()
+				.as_bool()
+		};
+		assert!(contained_in(&inside, &big_box));
+		assert!(!contained_in(&straddling, &big_box));
+		assert!(!contained_in(&big_box, &inside));
+	}
+
+	#[test]
+	pub(super) fn cloned_sphere_at_a_new_position() {
+		let client = test_client();
+		let original = create_sphere(&client, "instance_source", Vec3::ZERO, 0.5_f32);
+		signal(
+			&client,
+			"/field",
+			"cloneField",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push(original.get_path());
+				vec.push("instance");
+				vec.push("/");
+				push_to_vec!(
+					vec,
+					mint::Vector3::from(Vec3::X * 3_f32),
+					mint::Quaternion::from(Quat::IDENTITY)
+				);
+			}),
+		);
+		let clone = client.scenegraph.get_node("/field/instance").unwrap();
+		assert_near(
+			distance_at(&client, &original, Vec3::ZERO),
+			-0.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &clone, Vec3::X * 3_f32),
+			-0.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &clone, Vec3::ZERO),
+			2.5_f32,
+			0.0001_f32,
+		);
+		// the clone copied the radius rather than sharing it
+		set_radius(&client, &original, 1_f32);
+		assert_near(
+			distance_at(&client, &clone, Vec3::X * 3_f32),
+			-0.5_f32,
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	pub(super) fn frustum_cull_inside_outside_and_across() {
+		let client = test_client();
+		// a box shaped frustum looking down -Z, each plane facing inwards
+		let planes = [
+			(Vec3::X, 5_f32),
+			(-Vec3::X, 5_f32),
+			(Vec3::Y, 5_f32),
+			(-Vec3::Y, 5_f32),
+			(-Vec3::Z, -1_f32),
+			(Vec3::Z, 20_f32),
+		];
+		let cull = |name: &str, position: Vec3| {
+			let sphere = create_sphere(&client, name, position, 0.5_f32);
+			let cull = method(
+				&client,
+				sphere.get_path(),
+				"frustum_cull",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push("/");
+					let mut plane_vec = vec.start_vector();
+					for (normal, offset) in planes {
+						let mut plane = plane_vec.start_vector();
+						push_to_vec!(&mut plane, mint::Vector3::from(normal), offset);
+						plane.end_vector();
+					}
+					plane_vec.end_vector();
+				}),
+			)
+			.unwrap();
+			flexbuffers::Reader::get_root(cull.as_slice())
+				.unwrap()
+				.as_str()
+				.to_string()
+		};
+		assert_eq!(cull("in_view", -Vec3::Z * 10_f32), "inside");
+		assert_eq!(
+			cull("off_to_the_side", vec3(30_f32, 0_f32, -10_f32)),
+			"outside"
+		);
+		assert_eq!(cull("behind", Vec3::Z * 3_f32), "outside");
+		assert_eq!(
+			cull("on_the_edge", vec3(5_f32, 0_f32, -10_f32)),
+			"intersecting"
+		);
+	}
+
+	#[test]
+	pub(super) fn sphere_histogram_is_about_half_interior() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "histogrammed", Vec3::ZERO, 1_f32);
+		let count = 20000_u32;
+		let histogram = |buckets: u32| {
+			let histogram = method(
+				&client,
+				sphere.get_path(),
+				"distance_histogram",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push(buckets);
+					vec.push(count);
+				}),
+			)
+			.unwrap();
+			flexbuffers::Reader::get_root(histogram.as_slice())
+				.unwrap()
+				.as_vector()
+				.iter()
+				.map(|bucket| bucket.as_u32())
+				.collect::<Vec<_>>()
+		};
+		// the sphere fills pi / 6 of its bounding cube, and negative distances land in the lower half
+		let halves = histogram(2);
+		assert_eq!(halves.iter().sum::<u32>(), count);
+		let interior = halves[0] as f32 / count as f32;
+		assert_near(interior, std::f32::consts::PI / 6_f32, 0.02_f32);
+
+		let fine = histogram(8);
+		assert_eq!(fine.len(), 8);
+		assert_eq!(fine.iter().sum::<u32>(), count);
+		assert_eq!(fine[..4].iter().sum::<u32>(), halves[0]);
+	}
+
+	#[test]
+	pub(super) fn aim_at_turns_a_cylinder_towards_the_target() {
+		let client = test_client();
+		let handle = create_field(&client, "createCylinderField", "handle", Vec3::X, |vec| {
+			vec.push(2_f32);
+			vec.push(0.2_f32);
+		});
+		let target = vec3(1_f32, 3_f32, 3_f32);
+		signal(
+			&client,
+			handle.get_path(),
+			"aimAt",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("/");
+				push_to_vec!(vec, mint::Vector3::from(target));
+			}),
+		);
+		let axis = handle
+			.spatial
+			.get()
+			.unwrap()
+			.global_transform()
+			.transform_vector3(Vec3::Z)
+			.normalize();
+		let direction = (target - Vec3::X).normalize();
+		assert!(
+			axis.abs_diff_eq(direction, 0.0001_f32),
+			"{} vs {}",
+			axis,
+			direction
+		);
+		// the length now runs towards the target and the radius across it
+		assert!(distance_at(&client, &handle, Vec3::X + (direction * 0.9_f32)) < 0_f32);
+		assert!(distance_at(&client, &handle, Vec3::X + (Vec3::X * 0.9_f32)) > 0_f32);
+
+		let at_origin = flexbuffer_from_vector_arguments(|vec| {
+			vec.push("/");
+			push_to_vec!(vec, mint::Vector3::from(Vec3::X));
+		});
+		assert!(handle
+			.send_local_signal(client.clone(), "aimAt", &at_origin)
+			.is_err());
+		// a sphere has no axis to aim
+		let ball = create_sphere(&client, "ball", Vec3::ZERO, 1_f32);
+		let error = ball
+			.send_local_signal(client.clone(), "aimAt", &at_origin)
+			.unwrap_err();
+		assert!(error.to_string().contains("no natural axis"), "{}", error);
+	}
+
+	#[test]
+	pub(super) fn distances_in_spaces_match_single_queries() {
+		let client = test_client();
+		crate::nodes::spatial::create_interface(&client);
+		let heads = [
+			("head_a", Vec3::Y, Quat::from_rotation_y(0.5_f32), Vec3::ONE),
+			(
+				"head_b",
+				Vec3::X * 5_f32,
+				Quat::IDENTITY,
+				Vec3::splat(2_f32),
+			),
+		];
+		for (name, position, rotation, scale) in heads {
+			signal(
+				&client,
+				"/spatial",
+				"createSpatial",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push(name);
+					vec.push("/");
+					push_to_vec!(
+						vec,
+						mint::Vector3::from(position),
+						mint::Quaternion::from(rotation),
+						mint::Vector3::from(scale)
+					);
+				}),
+			);
+		}
+		let box_node = create_box(
+			&client,
+			"shared_zone",
+			vec3(2_f32, 0_f32, -1_f32),
+			Vec3::ONE,
+		);
+		let points = [-Vec3::Z, vec3(-1_f32, 0.2_f32, -0.5_f32)];
+		let distances_in_spaces = |points: &[Vec3]| {
+			method(
+				&client,
+				box_node.get_path(),
+				"distances_in_spaces",
+				&flexbuffer_from_vector_arguments(|vec| {
+					let mut spaces = vec.start_vector();
+					for (name, ..) in heads {
+						spaces.push(format!("/spatial/spatial/{}", name).as_str());
+					}
+					spaces.end_vector();
+					let mut point_vec = vec.start_vector();
+					for point in points {
+						push_to_vec!(&mut point_vec, mint::Vector3::from(*point));
+					}
+					point_vec.end_vector();
+				}),
+			)
+		};
+		let distances = distances_in_spaces(&points).unwrap();
+		let distances = flexbuffers::Reader::get_root(distances.as_slice())
+			.unwrap()
+			.as_vector();
+		assert_eq!(distances.len(), 2);
+		for ((distance, (name, ..)), point) in distances.iter().zip(heads).zip(points) {
+			let single = read_f32(
+				&method(
+					&client,
+					box_node.get_path(),
+					"distance",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push(format!("/spatial/spatial/{}", name).as_str());
+						push_to_vec!(vec, mint::Vector3::from(point));
+					}),
+				)
+				.unwrap(),
+			);
+			assert_near(distance.as_f32(), single, 0.0001_f32);
+		}
+		assert!(distances_in_spaces(&points[..1]).is_err());
+	}
+
+	#[test]
+	pub(super) fn local_distance_method_matches_the_field_space_path() {
+		let client = test_client();
+		let box_node = create_box(
+			&client,
+			"procedural",
+			Vec3::ZERO,
+			vec3(2_f32, 1_f32, 0.5_f32),
+		);
+		box_node
+			.spatial
+			.get()
+			.unwrap()
+			.set_local_transform(Mat4::from_scale_rotation_translation(
+				Vec3::splat(0.5_f32),
+				Quat::from_rotation_x(1.1_f32),
+				vec3(-2_f32, 4_f32, 1_f32),
+			));
+		let path = box_node.get_path();
+		for point in [
+			Vec3::ZERO,
+			vec3(1.2_f32, 0_f32, 0_f32),
+			vec3(0.5_f32, -0.7_f32, 3_f32),
+		] {
+			let local = read_f32(
+				&method(
+					&client,
+					path,
+					"local_distance",
+					&flexbuffer_from_vector_arguments(|vec| {
+						push_to_vec!(vec, mint::Vector3::from(point));
+					}),
+				)
+				.unwrap(),
+			);
+			let in_own_space = read_f32(
+				&method(
+					&client,
+					path,
+					"distance",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push(path);
+						push_to_vec!(vec, mint::Vector3::from(point));
+					}),
+				)
+				.unwrap(),
+			);
+			assert_eq!(local, in_own_space);
+			// the point isn't transformed, unlike the same point given in the root's space
+			assert!((local - distance_at(&client, &box_node, point)).abs() > 0.01_f32);
+		}
+	}
+
+	#[test]
+	pub(super) fn natural_axis_of_a_tall_box_and_a_sphere() {
+		let client = test_client();
+		let tall_box = create_box(&client, "pillar", Vec3::ZERO, vec3(0.5_f32, 3_f32, 1_f32));
+		let sphere = create_sphere(&client, "axisless", Vec3::ZERO, 1_f32);
+		let natural_axis = |node: &Node| {
+			read_vec3s(
+				&method(
+					&client,
+					node.get_path(),
+					"natural_axis",
+					&flexbuffer_from_vector_arguments(|vec| vec.push("/")),
+				)
+				.unwrap(),
+			)
+		};
+		let axis = natural_axis(&tall_box);
+		assert_eq!(axis.len(), 1);
+		assert!(axis[0].abs_diff_eq(Vec3::Y, 0.0001_f32), "{}", axis[0]);
+		// in the reference space, so turning the box turns the axis
+		tall_box
+			.spatial
+			.get()
+			.unwrap()
+			.set_local_transform(Mat4::from_rotation_z(std::f32::consts::FRAC_PI_2));
+		assert!(natural_axis(&tall_box)[0].abs_diff_eq(-Vec3::X, 0.0001_f32));
+		assert!(natural_axis(&sphere).is_empty());
+	}
+
+	#[test]
+	pub(super) fn camera_distance_matches_distance_at_the_near_plane() {
+		let client = test_client();
+		crate::nodes::spatial::create_interface(&client);
+		signal(
+			&client,
+			"/spatial",
+			"createSpatial",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("camera");
+				vec.push("/");
+				push_to_vec!(
+					vec,
+					mint::Vector3::from(vec3(0_f32, 1.5_f32, 2_f32)),
+					mint::Quaternion::from(Quat::from_rotation_y(0.3_f32)),
+					mint::Vector3::from(Vec3::ONE)
+				);
+			}),
+		);
+		let sphere = create_sphere(&client, "viewed", Vec3::Y * 1.5_f32, 0.5_f32);
+		let camera = "/spatial/spatial/camera";
+		let query = |method_name: &str, point: Option<Vec3>| {
+			read_f32(
+				&method(
+					&client,
+					sphere.get_path(),
+					method_name,
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push(camera);
+						if let Some(point) = point {
+							push_to_vec!(vec, mint::Vector3::from(point));
+						}
+					}),
+				)
+				.unwrap(),
+			)
+		};
+		let near_centre = -Vec3::Z * DEFAULT_NEAR_PLANE;
+		assert_eq!(
+			query("camera_distance", None),
+			query("distance", Some(near_centre))
+		);
+		let near_corner = vec3(0.05_f32, -0.03_f32, -DEFAULT_NEAR_PLANE);
+		assert_eq!(
+			query("camera_distance", Some(near_corner)),
+			query("distance", Some(near_corner))
+		);
+	}
+
+	#[test]
+	pub(super) fn gradient_magnitude_flags_inexact_fields() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "exact", Vec3::ZERO, 0.5_f32);
+		let gradient_magnitude = |node: &Arc<Node>, point: Vec3| {
+			read_f32(
+				&method(
+					&client,
+					node.get_path(),
+					"gradient_magnitude",
+					&point_args(point),
+				)
+				.unwrap(),
+			)
+		};
+		for point in [Vec3::X, Vec3::new(0.2, -0.3, 0.1), Vec3::splat(2_f32)] {
+			assert_near(gradient_magnitude(&sphere, point), 1_f32, 0.01_f32);
+		}
+
+		let left = create_sphere(&client, "left", Vec3::X * -0.6_f32, 0.5_f32);
+		let right = create_sphere(&client, "right", Vec3::X * 0.6_f32, 0.5_f32);
+		let union = create_field(&client, "createUnionField", "blended", Vec3::ZERO, |vec| {
+			let mut children = vec.start_vector();
+			children.push(left.get_path());
+			children.push(right.get_path());
+			children.end_vector();
+			vec.push(0.5_f32);
+		});
+		// between the spheres both distances blend, so the field bounds the distance loosely
+		let blended = gradient_magnitude(&union, Vec3::Y * 0.6_f32);
+		assert!(blended < 0.95_f32, "gradient magnitude {}", blended);
+		// far out along x only one sphere counts and the field is exact again
+		assert_near(gradient_magnitude(&union, Vec3::X * 3_f32), 1_f32, 0.01_f32);
+	}
+}
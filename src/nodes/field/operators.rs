@@ -0,0 +1,1415 @@
+use super::{
+	box_distance, flex_to_sample_seed, flex_to_scale, flex_to_size, flex_to_size_vec3, next_random,
+	DistanceCache, Field, FieldCommon, FieldTrait, FIELD_PARENT_PATH, FIELD_REGISTRY,
+};
+use crate::core::client::Client;
+use crate::nodes::core::{check_flex_args, flex_args, Node};
+use crate::nodes::spatial::{get_spatial_parent_flex, get_transform_pose_flex, Spatial};
+use anyhow::{anyhow, ensure, Result};
+use glam::{vec3a, Mat4, Vec3, Vec3A};
+use parking_lot::Mutex;
+use portable_atomic::AtomicF32;
+use std::sync::atomic::Ordering;
+use std::sync::{Arc, Weak};
+
+pub struct MirrorField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) child: Weak<Field>,
+	pub(super) axis: usize,
+}
+
+impl MirrorField {
+	/// Reflects `child` across this field's local plane perpendicular to `axis` (0, 1 or 2 for X, Y or Z).
+	pub fn add_to(node: &Arc<Node>, child: &Arc<Field>, axis: usize) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		ensure!(axis < 3, "Internal: Mirror axis out of range!");
+		let mirror_field = MirrorField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			child: Arc::downgrade(child),
+			axis,
+		};
+		mirror_field.add_field_methods(node);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::Mirror(mirror_field)));
+		Ok(())
+	}
+}
+
+impl FieldTrait for MirrorField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let child = match self.child.upgrade() {
+			Some(child) => child,
+			None => return f32::MAX,
+		};
+		let mut p = p;
+		p[self.axis] = p[self.axis].abs();
+		child.local_distance(child.local_point(self.space.as_ref(), p))
+	}
+	fn complexity(&self) -> u32 {
+		self.child
+			.upgrade()
+			.map_or(1, |child| child.complexity().saturating_add(1))
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+/// Uniformly scales a child field around this field's origin without touching any spatials.
+pub struct ScaleField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) child: Weak<Field>,
+	pub(super) scale: AtomicF32,
+}
+
+impl ScaleField {
+	pub fn add_to(node: &Arc<Node>, child: &Arc<Field>, scale: f32) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let scale_field = ScaleField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			child: Arc::downgrade(child),
+			scale: AtomicF32::new(scale),
+		};
+		scale_field.add_field_methods(node);
+		// named apart from the generic setScale, which scales this field's spatial instead
+		add_field_signal!(node, "setChildScale", ScaleField::set_scale_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::Scale(scale_field)));
+		Ok(())
+	}
+
+	pub fn set_scale(&self, scale: f32) {
+		self.scale.store(scale, Ordering::Relaxed);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_scale_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let scale = flex_to_scale(flexbuffers::Reader::get_root(data)?)?;
+		if let Field::Scale(scale_field) = node.field.get().unwrap().as_ref() {
+			scale_field.set_scale(scale);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for ScaleField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let child = match self.child.upgrade() {
+			Some(child) => child,
+			None => return f32::MAX,
+		};
+		// shrink the query into the child's unscaled space, then grow the distance back out
+		let scale = self.scale.load(Ordering::Relaxed);
+		child.local_distance(child.local_point(self.space.as_ref(), p / scale)) * scale
+	}
+	fn complexity(&self) -> u32 {
+		self.child
+			.upgrade()
+			.map_or(1, |child| child.complexity().saturating_add(1))
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+/// How much faster than the query point a unit frequency and amplitude value noise displacement
+/// can move, so warped distances can be scaled back into a bound on the true distance.
+const NOISE_LIPSCHITZ: f32 = 3_f32;
+
+/// Random value in -1..1 at a lattice point, fixed for a given seed.
+fn lattice_value(seed: u64, x: i32, y: i32, z: i32) -> f32 {
+	let mut state = seed
+		^ (x as u64).wrapping_mul(0x8DA6_B343)
+		^ (y as u64).wrapping_mul(0xD816_3841)
+		^ (z as u64).wrapping_mul(0xCB1A_B31F);
+	(next_random(&mut state) * 2_f32) - 1_f32
+}
+/// Smoothly interpolated value noise in -1..1 with lattice points 1 apart.
+fn value_noise(p: Vec3A, seed: u64) -> f32 {
+	let cell = p.floor();
+	let t = p - cell;
+	let t = t * t * (Vec3A::splat(3_f32) - (t * 2_f32));
+	let (x, y, z) = (cell.x as i32, cell.y as i32, cell.z as i32);
+	let lerp = |a: f32, b: f32, t: f32| a + ((b - a) * t);
+	let layer = |z: i32| {
+		lerp(
+			lerp(
+				lattice_value(seed, x, y, z),
+				lattice_value(seed, x + 1, y, z),
+				t.x,
+			),
+			lerp(
+				lattice_value(seed, x, y + 1, z),
+				lattice_value(seed, x + 1, y + 1, z),
+				t.x,
+			),
+			t.y,
+		)
+	};
+	lerp(layer(z), layer(z + 1), t.z)
+}
+
+/// Noise frequency and amplitude, which both have to be finite.
+fn flex_to_noise<B: flexbuffers::Buffer>(
+	frequency: flexbuffers::Reader<B>,
+	amplitude: flexbuffers::Reader<B>,
+) -> Result<(f32, f32)> {
+	let (frequency, amplitude) = (frequency.as_f32(), amplitude.as_f32());
+	ensure!(
+		frequency.is_finite() && amplitude.is_finite(),
+		"Noise frequency and amplitude must be finite"
+	);
+	Ok((frequency, amplitude))
+}
+
+/// Wobbles a child field by moving each query point by value noise before asking the child.
+/// That isn't a true distance anymore, so it's scaled down by how fast the noise can move the
+/// point to stay a conservative bound for marching, and keeping the amplitude small keeps that
+/// from slowing marches down much.
+pub struct NoiseWarpField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) child: Weak<Field>,
+	/// Frequency and amplitude.
+	pub(super) noise: Mutex<(f32, f32)>,
+	pub(super) seed: u64,
+}
+
+impl NoiseWarpField {
+	pub fn add_to(
+		node: &Arc<Node>,
+		child: &Arc<Field>,
+		frequency: f32,
+		amplitude: f32,
+		seed: u64,
+	) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let noise_warp_field = NoiseWarpField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			child: Arc::downgrade(child),
+			noise: Mutex::new((frequency, amplitude)),
+			seed,
+		};
+		noise_warp_field.add_field_methods(node);
+		add_field_signal!(node, "setNoise", NoiseWarpField::set_noise_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::NoiseWarp(noise_warp_field)));
+		Ok(())
+	}
+
+	pub fn set_noise(&self, frequency: f32, amplitude: f32) {
+		*self.noise.lock() = (frequency, amplitude);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_noise_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let flex_vec = flex_args(data, &["frequency", "amplitude"])?;
+		let (frequency, amplitude) = flex_to_noise(flex_vec.idx(0), flex_vec.idx(1))?;
+		if let Field::NoiseWarp(noise_warp_field) = node.field.get().unwrap().as_ref() {
+			noise_warp_field.set_noise(frequency, amplitude);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for NoiseWarpField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let child = match self.child.upgrade() {
+			Some(child) => child,
+			None => return f32::MAX,
+		};
+		let (frequency, amplitude) = *self.noise.lock();
+		let q = p * frequency;
+		// each axis gets its own noise by offsetting the seed
+		let displacement = vec3a(
+			value_noise(q, self.seed),
+			value_noise(q, self.seed.wrapping_add(1)),
+			value_noise(q, self.seed.wrapping_add(2)),
+		) * amplitude;
+		let lipschitz = 1_f32 + ((amplitude * frequency).abs() * NOISE_LIPSCHITZ);
+		child.local_distance(child.local_point(self.space.as_ref(), p + displacement)) / lipschitz
+	}
+	fn complexity(&self) -> u32 {
+		self.child
+			.upgrade()
+			.map_or(1, |child| child.complexity().saturating_add(1))
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+/// The part of a child field inside a box centered on this field, e.g. to make a slab into
+/// a finite floor patch.
+pub struct ClipField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) child: Weak<Field>,
+	pub(super) size: Mutex<Vec3>,
+}
+
+impl ClipField {
+	pub fn add_to(node: &Arc<Node>, child: &Arc<Field>, size: Vec3) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let clip_field = ClipField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			child: Arc::downgrade(child),
+			size: Mutex::new(size),
+		};
+		clip_field.add_field_methods(node);
+		add_field_signal!(node, "setSize", ClipField::set_size_flex);
+		let _ = node.field.set(FIELD_REGISTRY.add(Field::Clip(clip_field)));
+		Ok(())
+	}
+
+	pub fn set_size(&self, size: Vec3) {
+		*self.size.lock() = size;
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_size_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let root = flexbuffers::Reader::get_root(data)?;
+		let size = flex_to_size_vec3(root, "Size")?;
+		if let Field::Clip(clip_field) = node.field.get().unwrap().as_ref() {
+			clip_field.set_size(size.into());
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for ClipField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let child = match self.child.upgrade() {
+			Some(child) => child,
+			None => return f32::MAX,
+		};
+		let size = *self.size.lock();
+		child
+			.local_distance(child.local_point(self.space.as_ref(), p))
+			.max(box_distance(p, size))
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		self.size.lock().length() * 0.5_f32
+	}
+	fn complexity(&self) -> u32 {
+		self.child
+			.upgrade()
+			.map_or(1, |child| child.complexity().saturating_add(1))
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+/// Blends past either end would extrapolate the distances, which isn't a bound on anything.
+fn flex_to_blend<B: flexbuffers::Buffer>(flex: flexbuffers::Reader<B>) -> Result<f32> {
+	let blend = flex.as_f32();
+	ensure!(
+		(0_f32..=1_f32).contains(&blend),
+		"Blend must be between 0 and 1"
+	);
+	Ok(blend)
+}
+
+/// Morphs between two fields by interpolating their distances. That's a bound on the distance
+/// to the morphed surface rather than the exact distance, so marching it is conservative.
+pub struct BlendShapeField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) from: Weak<Field>,
+	pub(super) to: Weak<Field>,
+	pub(super) blend: AtomicF32,
+}
+
+impl BlendShapeField {
+	/// `blend` of 0 is exactly `from` and 1 is exactly `to`.
+	pub fn add_to(node: &Arc<Node>, from: &Arc<Field>, to: &Arc<Field>, blend: f32) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let blend_shape_field = BlendShapeField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			from: Arc::downgrade(from),
+			to: Arc::downgrade(to),
+			blend: AtomicF32::new(blend),
+		};
+		blend_shape_field.add_field_methods(node);
+		add_field_signal!(node, "setBlend", BlendShapeField::set_blend_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::BlendShape(blend_shape_field)));
+		Ok(())
+	}
+
+	pub fn set_blend(&self, blend: f32) {
+		self.blend.store(blend, Ordering::Relaxed);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_blend_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let blend = flex_to_blend(flexbuffers::Reader::get_root(data)?)?;
+		if let Field::BlendShape(blend_shape_field) = node.field.get().unwrap().as_ref() {
+			blend_shape_field.set_blend(blend);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for BlendShapeField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let child_distance =
+			|child: Arc<Field>| child.local_distance(child.local_point(self.space.as_ref(), p));
+		// if one side is gone just be the other one
+		match (
+			self.from.upgrade().map(child_distance),
+			self.to.upgrade().map(child_distance),
+		) {
+			(Some(from), Some(to)) => from + ((to - from) * self.blend.load(Ordering::Relaxed)),
+			(Some(distance), None) | (None, Some(distance)) => distance,
+			(None, None) => f32::MAX,
+		}
+	}
+	fn complexity(&self) -> u32 {
+		[&self.from, &self.to]
+			.iter()
+			.filter_map(|child| child.upgrade())
+			.map(|child| child.complexity())
+			.sum::<u32>()
+			.saturating_add(1)
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SmoothingKind {
+	/// Quadratic polynomial blend. Cheap and only touches the region within `smoothness` of
+	/// both surfaces, but its curvature isn't continuous.
+	Polynomial,
+	/// Exponential blend. Smooth everywhere, but every child affects the result a little
+	/// even far away, which tends to inflate the union slightly.
+	Exponential,
+	/// Power blend. Very round joins, but only meaningful outside the children so it falls
+	/// back to a plain min inside any of them.
+	Power,
+}
+impl SmoothingKind {
+	fn smooth_min(self, a: f32, b: f32, k: f32) -> f32 {
+		if k <= f32::EPSILON {
+			return a.min(b);
+		}
+		match self {
+			SmoothingKind::Polynomial => {
+				let h = (k - (a - b).abs()).max(0_f32) / k;
+				a.min(b) - (h * h * k * 0.25_f32)
+			}
+			SmoothingKind::Exponential => {
+				// offset by the min so the exponentials can't underflow to zero
+				let m = a.min(b);
+				m - (k * ((-(a - m) / k).exp2() + (-(b - m) / k).exp2()).log2())
+			}
+			SmoothingKind::Power => {
+				if a <= 0_f32 || b <= 0_f32 {
+					return a.min(b);
+				}
+				// (a^n * b^n / (a^n + b^n))^(1/n) with n = 1/k, rearranged so it can't overflow
+				let (min, max) = (a.min(b), a.max(b));
+				min * (1_f32 + (min / max).powf(1_f32 / k)).powf(-k)
+			}
+		}
+	}
+}
+
+/// Negative smoothness would push the union outside its children instead of rounding it.
+fn flex_to_smoothness<B: flexbuffers::Buffer>(flex: flexbuffers::Reader<B>) -> Result<f32> {
+	let smoothness = flex.as_f32();
+	ensure!(
+		smoothness.is_finite() && smoothness >= 0_f32,
+		"Smoothness must be finite and not negative"
+	);
+	Ok(smoothness)
+}
+
+/// Smooth union of other field nodes, blending within `smoothness` of their surfaces.
+pub struct UnionField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) children: Vec<Weak<Field>>,
+	pub(super) smoothing: Mutex<(SmoothingKind, f32)>,
+}
+
+impl UnionField {
+	pub fn add_to(node: &Arc<Node>, children: &[Arc<Field>], smoothness: f32) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let union_field = UnionField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			children: children.iter().map(Arc::downgrade).collect(),
+			smoothing: Mutex::new((SmoothingKind::Polynomial, smoothness)),
+		};
+		union_field.add_field_methods(node);
+		add_field_signal!(node, "setSmoothness", UnionField::set_smoothness_flex);
+		add_field_signal!(
+			node,
+			"setSmoothingKind",
+			UnionField::set_smoothing_kind_flex
+		);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::Union(union_field)));
+		Ok(())
+	}
+
+	pub fn set_smoothness(&self, smoothness: f32) {
+		self.smoothing.lock().1 = smoothness;
+		DistanceCache::invalidate_all();
+	}
+	pub fn set_smoothing_kind(&self, kind: SmoothingKind) {
+		self.smoothing.lock().0 = kind;
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_smoothness_flex(
+		node: &Node,
+		_calling_client: Arc<Client>,
+		data: &[u8],
+	) -> Result<()> {
+		let smoothness = flex_to_smoothness(flexbuffers::Reader::get_root(data)?)?;
+		if let Field::Union(union_field) = node.field.get().unwrap().as_ref() {
+			union_field.set_smoothness(smoothness);
+		}
+		Ok(())
+	}
+	pub fn set_smoothing_kind_flex(
+		node: &Node,
+		_calling_client: Arc<Client>,
+		data: &[u8],
+	) -> Result<()> {
+		let kind = match flexbuffers::Reader::get_root(data)?.get_str()? {
+			"polynomial" => SmoothingKind::Polynomial,
+			"exponential" => SmoothingKind::Exponential,
+			"power" => SmoothingKind::Power,
+			_ => return Err(anyhow!("Invalid smoothing kind")),
+		};
+		if let Field::Union(union_field) = node.field.get().unwrap().as_ref() {
+			union_field.set_smoothing_kind(kind);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for UnionField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let (kind, smoothness) = *self.smoothing.lock();
+		self.children
+			.iter()
+			.filter_map(Weak::upgrade)
+			.map(|child| child.local_distance(child.local_point(self.space.as_ref(), p)))
+			.reduce(|a, b| kind.smooth_min(a, b, smoothness))
+			.unwrap_or(f32::MAX)
+	}
+	fn complexity(&self) -> u32 {
+		self.children
+			.iter()
+			.filter_map(Weak::upgrade)
+			.map(|child| child.complexity())
+			.sum::<u32>()
+			.saturating_add(1)
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+#[derive(Clone)]
+pub enum CompoundShape {
+	Box(Vec3),
+	Sphere(f32),
+}
+impl CompoundShape {
+	fn bounding_radius(&self) -> f32 {
+		match self {
+			CompoundShape::Box(size) => size.length() * 0.5_f32,
+			CompoundShape::Sphere(radius) => *radius,
+		}
+	}
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		match self {
+			CompoundShape::Box(size) => box_distance(p, *size),
+			CompoundShape::Sphere(radius) => p.length() - radius,
+		}
+	}
+}
+
+#[derive(Clone, Copy)]
+pub enum CompoundOperator {
+	Union,
+	Intersection,
+	/// The first child minus all the others.
+	Subtraction,
+}
+
+/// Several shapes sharing one spatial, each with its own offset from it.
+/// Much lighter than a node per shape for complex static fields.
+pub struct CompoundField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) operator: CompoundOperator,
+	pub(super) children: Vec<(Mat4, CompoundShape)>,
+}
+
+impl CompoundField {
+	pub fn add_to(
+		node: &Arc<Node>,
+		operator: CompoundOperator,
+		children: Vec<(Mat4, CompoundShape)>,
+	) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		ensure!(!children.is_empty(), "Compound field has no children");
+		let compound_field = CompoundField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			operator,
+			// stored as compound to child space so queries don't invert every time
+			children: children
+				.into_iter()
+				.map(|(offset, shape)| (offset.inverse(), shape))
+				.collect(),
+		};
+		compound_field.add_field_methods(node);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::Compound(compound_field)));
+		Ok(())
+	}
+}
+
+impl FieldTrait for CompoundField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let mut distances = self
+			.children
+			.iter()
+			.map(|(offset, shape)| shape.local_distance(offset.transform_point3a(p)));
+		let first = distances.next().unwrap_or(f32::MAX);
+		match self.operator {
+			CompoundOperator::Union => distances.fold(first, f32::min),
+			CompoundOperator::Intersection => distances.fold(first, f32::max),
+			CompoundOperator::Subtraction => distances.fold(first, |a, b| a.max(-b)),
+		}
+	}
+	fn complexity(&self) -> u32 {
+		self.children.len() as u32
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		self.children
+			.iter()
+			.map(|(offset, shape)| {
+				offset.inverse().transform_point3a(Vec3A::ZERO).length() + shape.bounding_radius()
+			})
+			.fold(0_f32, f32::max)
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+pub fn create_compound_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_compound_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_compound_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&[
+			"name", "parent", "position", "rotation", "operator", "children",
+		],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = get_transform_pose_flex(&flex_vec.idx(2), &flex_vec.idx(3))?;
+	let operator = match flex_vec.idx(4).get_str()? {
+		"union" => CompoundOperator::Union,
+		"intersection" => CompoundOperator::Intersection,
+		"subtraction" => CompoundOperator::Subtraction,
+		_ => return Err(anyhow!("Invalid compound operator")),
+	};
+	let children = flex_vec
+		.idx(5)
+		.get_vector()?
+		.iter()
+		.map(|child| -> Result<(Mat4, CompoundShape)> {
+			let child = child.get_vector()?;
+			let offset = get_transform_pose_flex(&child.idx(1), &child.idx(2))?;
+			let shape = match child.idx(0).get_str()? {
+				"box" => CompoundShape::Box(flex_to_size_vec3(child.idx(3), "Size")?.into()),
+				"sphere" => CompoundShape::Sphere(flex_to_size(child.idx(3), "Radius")?),
+				_ => return Err(anyhow!("Invalid compound child shape")),
+			};
+			Ok((offset, shape))
+		})
+		.collect::<Result<Vec<_>>>()?;
+	// checked here too so a bad request can't leave a node without a field behind
+	ensure!(!children.is_empty(), "Compound field has no children");
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	CompoundField::add_to(&node, operator, children)?;
+	Ok(())
+}
+
+pub(super) fn get_child_field_flex(calling_client: &Arc<Client>, path: &str) -> Result<Arc<Field>> {
+	Ok(calling_client
+		.scenegraph
+		.get_node(path)
+		.ok_or_else(|| anyhow!("Child field node {} does not exist", path))?
+		.field
+		.get()
+		.ok_or_else(|| anyhow!("Child field node {} is not a field", path))?
+		.clone())
+}
+
+pub fn create_mirror_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_mirror_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_mirror_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "position", "rotation", "child", "axis"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = get_transform_pose_flex(&flex_vec.idx(2), &flex_vec.idx(3))?;
+	let child = get_child_field_flex(calling_client, flex_vec.idx(4).as_str())?;
+	let axis = match flex_vec.idx(5).get_str()? {
+		"x" => 0,
+		"y" => 1,
+		"z" => 2,
+		_ => return Err(anyhow!("Invalid mirror axis")),
+	};
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	MirrorField::add_to(&node, &child, axis)?;
+	Ok(())
+}
+
+pub fn create_blend_shape_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_blend_shape_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_blend_shape_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&[
+			"name", "parent", "position", "rotation", "from", "to", "blend",
+		],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = get_transform_pose_flex(&flex_vec.idx(2), &flex_vec.idx(3))?;
+	let from = get_child_field_flex(calling_client, flex_vec.idx(4).as_str())?;
+	let to = get_child_field_flex(calling_client, flex_vec.idx(5).as_str())?;
+	let blend = flex_to_blend(flex_vec.idx(6))?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	BlendShapeField::add_to(&node, &from, &to, blend)?;
+	Ok(())
+}
+
+pub fn create_scale_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_scale_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_scale_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "position", "rotation", "child", "scale"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = get_transform_pose_flex(&flex_vec.idx(2), &flex_vec.idx(3))?;
+	let child = get_child_field_flex(calling_client, flex_vec.idx(4).as_str())?;
+	let scale = flex_to_scale(flex_vec.idx(5))?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	ScaleField::add_to(&node, &child, scale)?;
+	Ok(())
+}
+
+pub fn create_noise_warp_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_noise_warp_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_noise_warp_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&[
+			"name",
+			"parent",
+			"position",
+			"rotation",
+			"child",
+			"frequency",
+			"amplitude",
+		],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = get_transform_pose_flex(&flex_vec.idx(2), &flex_vec.idx(3))?;
+	let child = get_child_field_flex(calling_client, flex_vec.idx(4).as_str())?;
+	let (frequency, amplitude) = flex_to_noise(flex_vec.idx(5), flex_vec.idx(6))?;
+	let seed = flex_to_sample_seed(flex_vec.idx(7));
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	NoiseWarpField::add_to(&node, &child, frequency, amplitude, seed)?;
+	Ok(())
+}
+
+pub fn create_clip_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_clip_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_clip_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "position", "rotation", "child", "size"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = get_transform_pose_flex(&flex_vec.idx(2), &flex_vec.idx(3))?;
+	let child = get_child_field_flex(calling_client, flex_vec.idx(4).as_str())?;
+	let size = flex_to_size_vec3(flex_vec.idx(5), "Size")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	ClipField::add_to(&node, &child, size.into())?;
+	Ok(())
+}
+
+pub fn create_union_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_union_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_union_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "position", "rotation", "children"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = get_transform_pose_flex(&flex_vec.idx(2), &flex_vec.idx(3))?;
+	let children = flex_vec
+		.idx(4)
+		.get_vector()?
+		.iter()
+		.map(|child| get_child_field_flex(calling_client, child.as_str()))
+		.collect::<Result<Vec<_>>>()?;
+	ensure!(!children.is_empty(), "Union field has no children");
+	let smoothness = flex_to_smoothness(flex_vec.idx(5))?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	UnionField::add_to(&node, &children, smoothness)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::nodes::field::random_direction;
+	use crate::nodes::field::tests::*;
+	use glam::{vec3, Quat};
+	use libstardustxr::{flex_to_vec3, push_to_vec};
+
+	#[test]
+	fn compound_of_boxes_matches_the_nearest_box_node() {
+		let client = test_client();
+		let boxes = [
+			(Vec3::ZERO, vec3(1_f32, 1_f32, 1_f32)),
+			(vec3(1_f32, 0.5_f32, 0_f32), vec3(2_f32, 0.2_f32, 0.4_f32)),
+			(vec3(-0.5_f32, 0_f32, 1_f32), vec3(0.3_f32, 2_f32, 0.3_f32)),
+		];
+		create_field(
+			&client,
+			"createCompoundField",
+			"compound",
+			Vec3::ZERO,
+			|vec| {
+				vec.push("union");
+				let mut children = vec.start_vector();
+				for (position, size) in boxes {
+					let mut child = children.start_vector();
+					child.push("box");
+					push_to_vec!(
+						&mut child,
+						mint::Vector3::from(position),
+						mint::Quaternion::from(Quat::IDENTITY),
+						mint::Vector3::from(size)
+					);
+					child.end_vector();
+				}
+				children.end_vector();
+			},
+		);
+		let compound = client.scenegraph.get_node("/field/compound").unwrap();
+		let box_nodes: Vec<Arc<Node>> = boxes
+			.iter()
+			.enumerate()
+			.map(|(i, (position, size))| {
+				create_box(&client, &format!("part_{}", i), *position, *size)
+			})
+			.collect();
+
+		// a grid through and around all three boxes
+		let steps = [-3_f32, -1.5, 0_f32, 0.4, 1.5, 3_f32];
+		for x in steps {
+			for y in steps {
+				for z in steps {
+					let point = vec3(x, y, z);
+					let nearest = box_nodes
+						.iter()
+						.map(|node| distance_at(&client, node, point))
+						.fold(f32::INFINITY, f32::min);
+					assert_near(distance_at(&client, &compound, point), nearest, 0.0001_f32);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn mirror_makes_an_off_center_sphere_symmetric() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "mirrored_child", Vec3::X * 2_f32, 0.5_f32);
+		let mirror = create_field(&client, "createMirrorField", "mirror", Vec3::ZERO, |vec| {
+			vec.push(sphere.get_path());
+			vec.push("x");
+		});
+		for point in [
+			vec3(2_f32, 0.3_f32, 0_f32),
+			vec3(1_f32, 0.5_f32, 0.2_f32),
+			vec3(3_f32, -1_f32, 0_f32),
+		] {
+			let mirrored_point = point * vec3(-1_f32, 1_f32, 1_f32);
+			let distance = distance_at(&client, &mirror, point);
+			assert_near(distance, distance_at(&client, &sphere, point), 0.0001_f32);
+			assert_near(
+				distance_at(&client, &mirror, mirrored_point),
+				distance,
+				0.0001_f32,
+			);
+		}
+		assert!(distance_at(&client, &sphere, -Vec3::X * 2_f32) > 3_f32);
+		assert_near(
+			distance_at(&client, &mirror, -Vec3::X * 2_f32),
+			-0.5_f32,
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	fn every_smoothing_kind_reduces_to_min() {
+		let pairs = [
+			(0.3_f32, 0.7_f32),
+			(0.5, 0.5),
+			(-0.2, 0.4),
+			(1.0, 1.2),
+			(-0.6, -0.1),
+		];
+		for kind in [
+			SmoothingKind::Polynomial,
+			SmoothingKind::Exponential,
+			SmoothingKind::Power,
+		] {
+			for (a, b) in pairs {
+				let mut last_error = f32::INFINITY;
+				for smoothness in [0.1_f32, 0.01, 0.001] {
+					let error = (kind.smooth_min(a, b, smoothness) - a.min(b)).abs();
+					assert!(
+						error <= smoothness,
+						"{} off min({}, {}) by {}",
+						smoothness,
+						a,
+						b,
+						error
+					);
+					assert!(error <= last_error);
+					last_error = error;
+				}
+				assert_eq!(kind.smooth_min(a, b, 0_f32), a.min(b));
+			}
+		}
+	}
+
+	#[test]
+	fn invalid_union_smoothness_is_rejected() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "smoothed", Vec3::ZERO, 0.5_f32);
+		let union = create_field(&client, "createUnionField", "rounded", Vec3::ZERO, |vec| {
+			let mut children = vec.start_vector();
+			children.push(sphere.get_path());
+			children.end_vector();
+			vec.push(0.1_f32);
+		});
+		for smoothness in [-0.1_f32, f32::NAN, f32::INFINITY] {
+			assert!(union
+				.send_local_signal(
+					client.clone(),
+					"setSmoothness",
+					&flexbuffers::singleton(smoothness)
+				)
+				.is_err());
+			assert!(
+				try_create_field(&client, "createUnionField", "jagged", Vec3::ZERO, |vec| {
+					let mut children = vec.start_vector();
+					children.push(sphere.get_path());
+					children.end_vector();
+					vec.push(smoothness);
+				})
+				.is_err()
+			);
+			assert!(client.scenegraph.get_node("/field/jagged").is_none());
+		}
+		assert_near(distance_at(&client, &union, Vec3::X), 0.5_f32, 0.0001_f32);
+	}
+
+	#[test]
+	fn blend_shape_interpolates_its_children() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "morph_from", Vec3::ZERO, 1_f32);
+		let box_node = create_box(&client, "morph_to", Vec3::ZERO, Vec3::splat(3_f32));
+		let blend = create_field(
+			&client,
+			"createBlendShapeField",
+			"morph",
+			Vec3::ZERO,
+			|vec| {
+				vec.push(sphere.get_path());
+				vec.push(box_node.get_path());
+				vec.push(0_f32);
+			},
+		);
+		let point = vec3(2_f32, 1_f32, 0.5_f32);
+		let from = distance_at(&client, &sphere, point);
+		let to = distance_at(&client, &box_node, point);
+		assert!((from - to).abs() > 0.1_f32);
+
+		assert_near(distance_at(&client, &blend, point), from, 0.0001_f32);
+		signal(
+			&client,
+			blend.get_path(),
+			"setBlend",
+			&flexbuffers::singleton(1_f32),
+		);
+		assert_near(distance_at(&client, &blend, point), to, 0.0001_f32);
+		signal(
+			&client,
+			blend.get_path(),
+			"setBlend",
+			&flexbuffers::singleton(0.5_f32),
+		);
+		let halfway = distance_at(&client, &blend, point);
+		assert!(halfway > from.min(to) && halfway < from.max(to));
+		assert_near(halfway, (from + to) * 0.5_f32, 0.0001_f32);
+
+		for blend_value in [-0.5_f32, 1.5_f32, f32::NAN] {
+			assert!(blend
+				.send_local_signal(
+					client.clone(),
+					"setBlend",
+					&flexbuffers::singleton(blend_value)
+				)
+				.is_err());
+			assert!(try_create_field(
+				&client,
+				"createBlendShapeField",
+				"overblended",
+				Vec3::ZERO,
+				|vec| {
+					vec.push(sphere.get_path());
+					vec.push(box_node.get_path());
+					vec.push(blend_value);
+				}
+			)
+			.is_err());
+			assert!(client.scenegraph.get_node("/field/overblended").is_none());
+		}
+		// rejected blends leave the last one in place
+		assert_near(distance_at(&client, &blend, point), halfway, 0.0001_f32);
+	}
+
+	#[test]
+	fn scaled_sphere_keeps_exact_distances() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "unscaled", Vec3::ZERO, 1_f32);
+		let scaled = create_field(&client, "createScaleField", "grown", Vec3::ZERO, |vec| {
+			vec.push(sphere.get_path());
+			vec.push(2_f32);
+		});
+		let point = vec3(3_f32, 4_f32, 0_f32);
+		assert_near(distance_at(&client, &scaled, point), 3_f32, 0.0001_f32);
+		assert_near(
+			distance_at(&client, &scaled, Vec3::X * 0.5_f32),
+			-1.5_f32,
+			0.0001_f32,
+		);
+
+		// a unit gradient means the normal is exactly radial and distance changes one for one
+		let normal = method(&client, scaled.get_path(), "normal", &point_args(point)).unwrap();
+		let normal: Vec3 = flex_to_vec3!(flexbuffers::Reader::get_root(normal.as_slice()).unwrap())
+			.unwrap()
+			.into();
+		assert!(
+			normal.abs_diff_eq(point.normalize(), 0.001_f32),
+			"{}",
+			normal
+		);
+		let step = 0.01_f32;
+		assert_near(
+			distance_at(&client, &scaled, point + (normal * step))
+				- distance_at(&client, &scaled, point),
+			step,
+			0.0001_f32,
+		);
+
+		signal(
+			&client,
+			scaled.get_path(),
+			"setChildScale",
+			&flexbuffers::singleton(0.5_f32),
+		);
+		assert_near(distance_at(&client, &scaled, point), 4.5_f32, 0.0001_f32);
+	}
+
+	#[test]
+	fn invalid_scales_are_rejected_before_creating_the_node() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "to_scale", Vec3::ZERO, 1_f32);
+		for (name, scale) in [
+			("flat", 0_f32),
+			("flipped", -1_f32),
+			("nan_scale", f32::NAN),
+		] {
+			assert!(
+				try_create_field(&client, "createScaleField", name, Vec3::ZERO, |vec| {
+					vec.push(sphere.get_path());
+					vec.push(scale);
+				})
+				.is_err(),
+				"{}",
+				name
+			);
+			assert!(client
+				.scenegraph
+				.get_node(&format!("/field/{}", name))
+				.is_none());
+		}
+
+		let scaled = create_field(&client, "createScaleField", "scaled", Vec3::ZERO, |vec| {
+			vec.push(sphere.get_path());
+			vec.push(1_f32);
+		});
+		assert!(scaled
+			.send_local_signal(
+				client.clone(),
+				"setChildScale",
+				&flexbuffers::singleton(0_f32)
+			)
+			.is_err());
+	}
+
+	#[test]
+	fn noise_warp_stays_near_its_child() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "wobbled", Vec3::ZERO, 1_f32);
+		let warp = |name: &str, amplitude: f32| {
+			create_field(&client, "createNoiseWarpField", name, Vec3::ZERO, |vec| {
+				vec.push(sphere.get_path());
+				vec.push(2_f32);
+				vec.push(amplitude);
+			})
+		};
+		let still = warp("still", 0_f32);
+		let (frequency, amplitude) = (2_f32, 0.1_f32);
+		let wobbly = warp("wobbly", amplitude);
+		let lipschitz = 1_f32 + (amplitude * frequency * NOISE_LIPSCHITZ);
+
+		let mut state = 5_u64;
+		for _ in 0..100 {
+			let point =
+				Vec3::from(random_direction(&mut state)) * (next_random(&mut state) * 3_f32);
+			let child = distance_at(&client, &sphere, point);
+			assert_eq!(distance_at(&client, &still, point), child);
+			// the point moves at most the amplitude along each axis before the child is asked
+			let warped = distance_at(&client, &wobbly, point);
+			assert!(warped.is_finite());
+			assert!(
+				((warped * lipschitz) - child).abs() <= (amplitude * 3_f32.sqrt()) + 0.0001_f32,
+				"{} warped to {} at {}",
+				child,
+				warped,
+				point
+			);
+		}
+	}
+
+	#[test]
+	fn non_finite_noise_is_rejected_before_creating_the_node() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "unwarped", Vec3::ZERO, 1_f32);
+		for (name, frequency, amplitude) in [
+			("nan_frequency", f32::NAN, 0.1_f32),
+			("infinite_amplitude", 2_f32, f32::INFINITY),
+		] {
+			assert!(
+				try_create_field(&client, "createNoiseWarpField", name, Vec3::ZERO, |vec| {
+					vec.push(sphere.get_path());
+					vec.push(frequency);
+					vec.push(amplitude);
+				})
+				.is_err()
+			);
+			assert!(client
+				.scenegraph
+				.get_node(&format!("/field/{}", name))
+				.is_none());
+		}
+	}
+
+	#[test]
+	fn clip_turns_a_slab_into_a_floor_patch() {
+		let client = test_client();
+		let floor = create_field(
+			&client,
+			"createSlabField",
+			"endless_floor",
+			Vec3::ZERO,
+			|vec| {
+				vec.push(0.2_f32);
+			},
+		);
+		let patch = create_field(
+			&client,
+			"createClipField",
+			"floor_patch",
+			Vec3::ZERO,
+			|vec| {
+				vec.push(floor.get_path());
+				push_to_vec!(vec, mint::Vector3::from(Vec3::ONE));
+			},
+		);
+		for point in [Vec3::ZERO, vec3(0.4_f32, 0_f32, -0.4_f32)] {
+			assert_near(distance_at(&client, &patch, point), -0.1_f32, 0.0001_f32);
+		}
+		// still inside the slab, but not inside the patch
+		for point in [
+			Vec3::X * 3_f32,
+			vec3(-0.6_f32, 0_f32, 0_f32),
+			Vec3::Z * 10_f32,
+		] {
+			assert!(distance_at(&client, &floor, point) < 0_f32);
+			assert!(
+				distance_at(&client, &patch, point) > 0_f32,
+				"{} is inside",
+				point
+			);
+		}
+		assert_near(
+			distance_at(&client, &patch, Vec3::X * 3_f32),
+			2.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &patch, Vec3::Y * 2_f32),
+			1.9_f32,
+			0.0001_f32,
+		);
+	}
+}
@@ -0,0 +1,891 @@
+use super::{flex_to_finite_vec3, get_reference_space_flex, Field, FieldTrait};
+use crate::core::client::Client;
+use crate::nodes::core::{flex_args, Node};
+use crate::nodes::spatial::Spatial;
+use anyhow::{anyhow, Result};
+use glam::{Mat4, Vec3, Vec3A};
+use libstardustxr::flex::flexbuffer_from_vector_arguments;
+use libstardustxr::fusion::flex::FlexBuffable;
+use libstardustxr::push_to_vec;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Reads a ray from the first 4 arguments: ray space path, origin, direction and an optional step budget.
+fn get_ray_flex<B: flexbuffers::Buffer>(
+	calling_client: &Arc<Client>,
+	flex_vec: &flexbuffers::VectorReader<B>,
+) -> Result<Ray> {
+	let ray_space = calling_client
+		.scenegraph
+		.get_node(&flex_vec.idx(0).as_str())
+		.ok_or_else(|| anyhow!("Ray space node does not exist"))?
+		.spatial
+		.get()
+		.ok_or_else(|| anyhow!("Ray space node does not have a spatial"))?
+		.clone();
+	Ok(Ray {
+		origin: flex_to_finite_vec3(flex_vec.idx(1), "Origin")?.into(),
+		direction: flex_to_finite_vec3(flex_vec.idx(2), "Direction")?.into(),
+		space: ray_space,
+		max_steps: Some(flex_vec.idx(3).as_u32()).filter(|steps| *steps > 0),
+	})
+}
+
+#[cfg(feature = "field_debug")]
+pub(super) fn field_ray_march_trace_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["ray space", "origin", "direction"])?;
+	let ray = get_ray_flex(&calling_client, &flex_vec)?;
+
+	let (_, trace) = ray_march_trace(ray, node.field.get().unwrap());
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for step in trace {
+			push_to_vec!(
+				vec,
+				mint::Vector3::from(step.point),
+				step.distance,
+				step.march_distance
+			);
+		}
+	}))
+}
+pub(super) fn field_ray_march_all_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["ray space", "origin", "direction"])?;
+	let ray = get_ray_flex(&calling_client, &flex_vec)?;
+
+	let crossings = ray_march_all(&ray, node.field.get().unwrap());
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		for crossing in crossings {
+			push_to_vec!(vec, mint::Vector3::from(crossing.point), crossing.entering);
+		}
+	}))
+}
+pub(super) fn field_ray_march_exit_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["ray space", "origin", "direction"])?;
+	let ray = get_ray_flex(&calling_client, &flex_vec)?;
+
+	let exit = ray_march_exit(&ray, node.field.get().unwrap());
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		if let Some(exit) = exit {
+			push_to_vec!(vec, mint::Vector3::from(exit.point), exit.length);
+		}
+	}))
+}
+pub(super) fn field_ray_blocked_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(
+		data,
+		&[
+			"ray space",
+			"origin",
+			"direction",
+			"max steps",
+			"max length",
+		],
+	)?;
+	let ray = get_ray_flex(&calling_client, &flex_vec)?;
+	let max_length = flex_vec.idx(4).as_f32();
+
+	let blocked = ray_blocked(&ray, node.field.get().unwrap(), max_length);
+	Ok(FlexBuffable::from(blocked).build_singleton())
+}
+pub(super) fn field_surface_point_along_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["ray space", "origin", "direction"])?;
+	let ray = get_ray_flex(&calling_client, &flex_vec)?;
+
+	let point = surface_point_along(&ray, node.field.get().unwrap());
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		if let Some(point) = point {
+			push_to_vec!(vec, mint::Vector3::from(point));
+		}
+	}))
+}
+pub(super) fn field_vertical_gap_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(data, &["reference space", "point"])?;
+	let reference_space = get_reference_space_flex(&calling_client, flex_vec.idx(0).as_str())?;
+	let point = flex_to_finite_vec3(flex_vec.idx(1), "Point")?;
+
+	let gap = vertical_gap(reference_space, point.into(), node.field.get().unwrap());
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		if let Some(gap) = gap {
+			vec.push(gap);
+		}
+	}))
+}
+pub(super) fn field_ray_interior_length_flex(
+	node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<Vec<u8>> {
+	let flex_vec = flex_args(
+		data,
+		&[
+			"ray space",
+			"origin",
+			"direction",
+			"max steps",
+			"max length",
+		],
+	)?;
+	let ray = get_ray_flex(&calling_client, &flex_vec)?;
+	let max_length = flex_vec.idx(4).as_f32();
+
+	let interior_length = ray_interior_length(&ray, node.field.get().unwrap(), max_length);
+	Ok(FlexBuffable::from(interior_length).build_singleton())
+}
+pub struct Ray {
+	pub origin: Vec3,
+	pub direction: Vec3,
+	pub space: Arc<Spatial>,
+	/// Overrides the field's own step budget, see `FieldTrait::ray_steps`.
+	pub max_steps: Option<u32>,
+}
+impl Ray {
+	/// The ray's direction in the field's space and how long it is there,
+	/// so a field space distance `d` is `d / length` multiples of `direction` along the ray.
+	fn field_direction(&self, ray_to_field_matrix: Mat4) -> (Vec3A, f32) {
+		let direction = ray_to_field_matrix.transform_vector3a(self.direction.into());
+		(direction, direction.length().max(f32::MIN_POSITIVE))
+	}
+	fn max_steps(&self, field: &dyn FieldTrait) -> u32 {
+		self.max_steps
+			.unwrap_or_else(|| field.ray_steps())
+			.min(MAX_RAY_STEPS)
+	}
+}
+
+/// Lengths along the ray are in the ray's space, as multiples of `ray.direction`,
+/// so `ray.origin + (ray.direction * ray_length)` is where the march ended in the ray's space.
+pub struct RayMarchResult {
+	pub ray: Ray,
+	/// Closest approach to the surface, in the field's space.
+	pub distance: f32,
+	pub deepest_point_distance: f32,
+	pub ray_length: f32,
+	/// `ray_length` as measured in the field's space, which differs when the spaces are scaled.
+	pub field_space_ray_length: f32,
+	pub ray_steps: u32,
+	/// How far along the ray is known to be empty space, to resume a similar ray from.
+	pub clear_length: f32,
+	/// Whether any step landed inside the field, even if the closest approach didn't look like it.
+	pub entered_interior: bool,
+	pub termination: MarchTermination,
+}
+
+pub enum MarchTermination {
+	MaxSteps,
+	MaxLength,
+	/// Took longer than `MAX_RAY_MARCH_TIME`, so the result only covers part of the ray.
+	Timeout,
+}
+
+// const MIN_RAY_STEPS: u32 = 0;
+pub(super) const RAY_STEPS_PER_COMPLEXITY: u32 = 100;
+pub(super) const MAX_RAY_STEPS: u32 = 10000;
+
+pub(super) const MIN_RAY_MARCH: f32 = 0.001_f32;
+const MAX_RAY_MARCH: f32 = f32::MAX;
+
+// const MIN_RAY_LENGTH: f32 = 0_f32;
+const MAX_RAY_LENGTH: f32 = 1000_f32;
+
+const MAX_RAY_MARCH_TIME: Duration = Duration::from_millis(5);
+const RAY_STEPS_PER_TIME_CHECK: u32 = 16;
+
+pub fn ray_march(ray: Ray, field: &Field) -> RayMarchResult {
+	ray_march_from(ray, field, 0_f32)
+}
+
+/// Ray march starting `start_length` along the ray, usually a previous result's `clear_length`.
+/// If that point is already inside the field the whole ray is marched instead.
+///
+/// Nothing before `start_length` is looked at again, so if the field has moved or grown into
+/// that part of the ray since the earlier march (say a thin wall now sits between the origin
+/// and the seed) it is missed. Only seed from a result taken while the field was unchanged.
+pub fn ray_march_from(ray: Ray, field: &Field, start_length: f32) -> RayMarchResult {
+	ray_march_recording(ray, &**field, start_length, None)
+}
+
+pub struct RayMarchStep {
+	/// Where the step started, in the field's space.
+	pub point: Vec3,
+	pub distance: f32,
+	pub march_distance: f32,
+}
+
+/// `ray_march` that also returns every step it took, for seeing why a march went wrong.
+pub fn ray_march_trace(ray: Ray, field: &Field) -> (RayMarchResult, Vec<RayMarchStep>) {
+	let mut trace = Vec::new();
+	let result = ray_march_recording(ray, &**field, 0_f32, Some(&mut trace));
+	(result, trace)
+}
+
+fn ray_march_recording(
+	ray: Ray,
+	field: &dyn FieldTrait,
+	start_length: f32,
+	mut trace: Option<&mut Vec<RayMarchStep>>,
+) -> RayMarchResult {
+	let mut result = RayMarchResult {
+		ray,
+		distance: f32::MAX,
+		deepest_point_distance: 0_f32,
+		ray_length: 0_f32,
+		field_space_ray_length: 0_f32,
+		ray_steps: 0,
+		clear_length: 0_f32,
+		entered_interior: false,
+		termination: MarchTermination::MaxSteps,
+	};
+
+	let ray_to_field_matrix =
+		Spatial::space_to_space_matrix(Some(&result.ray.space), Some(field.spatial_ref()));
+	let mut ray_point = ray_to_field_matrix.transform_point3a(result.ray.origin.into());
+	let (ray_direction, field_scale) = result.ray.field_direction(ray_to_field_matrix);
+
+	let start_point = ray_point + (ray_direction * start_length);
+	if start_length > 0_f32 && field.local_distance(start_point) > MIN_RAY_MARCH {
+		ray_point = start_point;
+		result.ray_length = start_length;
+		result.clear_length = start_length;
+	}
+
+	let mut clear = true;
+	let max_steps = result.ray.max_steps(field);
+	// deeply nested fields can make every step expensive, so don't let a march stall everything,
+	// checking the clock more often the longer each step could take
+	let steps_per_time_check = (RAY_STEPS_PER_TIME_CHECK / field.complexity().max(1)).max(1);
+	let start_time = Instant::now();
+	while result.ray_steps < max_steps {
+		if result.ray_length >= MAX_RAY_LENGTH {
+			result.termination = MarchTermination::MaxLength;
+			break;
+		}
+		if result.ray_steps > 0
+			&& result.ray_steps % steps_per_time_check == 0
+			&& start_time.elapsed() > MAX_RAY_MARCH_TIME
+		{
+			result.termination = MarchTermination::Timeout;
+			break;
+		}
+		let distance = field.local_distance(ray_point);
+		let march_distance = distance.clamp(MIN_RAY_MARCH, MAX_RAY_MARCH);
+		if let Some(trace) = trace.as_mut() {
+			trace.push(RayMarchStep {
+				point: ray_point.into(),
+				distance,
+				march_distance,
+			});
+		}
+
+		result.entered_interior |= distance < 0_f32;
+		clear &= distance > MIN_RAY_MARCH;
+		if clear {
+			result.clear_length = result.ray_length;
+		}
+
+		let ray_step = march_distance / field_scale;
+		result.ray_length += ray_step;
+		ray_point += ray_direction * ray_step;
+
+		if result.distance > distance {
+			result.deepest_point_distance = result.ray_length;
+		}
+		result.distance = distance.min(result.distance);
+
+		result.ray_steps += 1;
+	}
+
+	result.field_space_ray_length = result.ray_length * field_scale;
+	result
+}
+
+pub struct RayCrossing {
+	/// Where the ray crossed the surface, in the ray's space.
+	pub point: Vec3,
+	/// Length along the ray to `point`, as a multiple of `ray.direction` like `RayMarchResult`'s lengths.
+	pub length: f32,
+	pub entering: bool,
+}
+
+pub(super) const MAX_RAY_CROSSINGS: usize = 64;
+
+/// March the whole ray, recording every time it crosses the field's surface.
+pub fn ray_march_all(ray: &Ray, field: &Field) -> Vec<RayCrossing> {
+	let mut crossings = Vec::new();
+
+	let ray_to_field_matrix =
+		Spatial::space_to_space_matrix(Some(&ray.space), Some(field.spatial_ref()));
+	let field_to_ray_matrix = ray_to_field_matrix.inverse();
+	let mut ray_point = ray_to_field_matrix.transform_point3a(ray.origin.into());
+	let (ray_direction, field_scale) = ray.field_direction(ray_to_field_matrix);
+
+	let mut ray_steps = 0;
+	let mut ray_length = 0_f32;
+	let mut inside = field.local_distance(ray_point) < 0_f32;
+	let max_steps = ray.max_steps(&**field);
+	while ray_steps < max_steps
+		&& ray_length < MAX_RAY_LENGTH
+		&& crossings.len() < MAX_RAY_CROSSINGS
+	{
+		let distance = field.local_distance(ray_point);
+		if (distance < 0_f32) != inside {
+			inside = !inside;
+			crossings.push(RayCrossing {
+				point: field_to_ray_matrix.transform_point3a(ray_point).into(),
+				length: ray_length,
+				entering: inside,
+			});
+		}
+		let march_distance = distance.abs().clamp(MIN_RAY_MARCH, MAX_RAY_MARCH);
+
+		let ray_step = march_distance / field_scale;
+		ray_length += ray_step;
+		ray_point += ray_direction * ray_step;
+		ray_steps += 1;
+	}
+
+	crossings
+}
+
+pub struct RayExit {
+	/// Where the ray leaves the field, in the ray's space.
+	pub point: Vec3,
+	/// Length along the ray to `point`, as a multiple of `ray.direction` like `RayMarchResult`'s lengths.
+	pub length: f32,
+}
+
+/// Where a ray first leaves the field, meant for rays that start inside it.
+pub fn ray_march_exit(ray: &Ray, field: &Field) -> Option<RayExit> {
+	ray_march_all(ray, field)
+		.into_iter()
+		.find(|crossing| !crossing.entering)
+		.map(|crossing| RayExit {
+			point: crossing.point,
+			length: crossing.length,
+		})
+}
+
+/// Projects the ray's origin onto the surface along the ray's direction instead of the gradient,
+/// e.g. straight down to place something on the field. In the ray's space.
+pub fn surface_point_along(ray: &Ray, field: &Field) -> Option<Vec3> {
+	ray_march_all(ray, field)
+		.into_iter()
+		.next()
+		.map(|crossing| crossing.point)
+}
+
+/// Height of `p` above the surface straight down along the space's -Y (or up along +Y when
+/// `p` is inside), negative when it's below. Differs from the distance on sloped surfaces.
+pub fn vertical_gap(space: Arc<Spatial>, p: Vec3, field: &Field) -> Option<f32> {
+	let inside = field.distance(&space, p.into()) < 0_f32;
+	let ray = Ray {
+		origin: p,
+		direction: if inside { Vec3::Y } else { -Vec3::Y },
+		space,
+		max_steps: None,
+	};
+	surface_point_along(&ray, field).map(|point| p.y - point.y)
+}
+
+/// Total length of the ray within `max_length` of its origin that's inside the field, all in the ray's space.
+pub fn ray_interior_length(ray: &Ray, field: &Field, max_length: f32) -> f32 {
+	let mut interior_length = 0_f32;
+	let mut entered_at = (field.distance(&ray.space, ray.origin.into()) < 0_f32).then(|| 0_f32);
+	for crossing in ray_march_all(ray, field) {
+		let length = crossing.point.distance(ray.origin).min(max_length);
+		match (crossing.entering, entered_at) {
+			(true, None) => entered_at = Some(length),
+			(false, Some(entry)) => {
+				interior_length += length - entry;
+				entered_at = None;
+			}
+			_ => (),
+		}
+	}
+	if let Some(entry) = entered_at {
+		interior_length += max_length - entry;
+	}
+	interior_length
+}
+
+/// Whether the ray hits the field within `max_length` (a multiple of `ray.direction`) of its origin,
+/// stopping as soon as it does rather than marching the rest of the ray.
+pub fn ray_blocked(ray: &Ray, field: &Field, max_length: f32) -> bool {
+	let ray_to_field_matrix =
+		Spatial::space_to_space_matrix(Some(&ray.space), Some(field.spatial_ref()));
+	let mut ray_point = ray_to_field_matrix.transform_point3a(ray.origin.into());
+	let (ray_direction, field_scale) = ray.field_direction(ray_to_field_matrix);
+
+	let mut ray_steps = 0;
+	let mut ray_length = 0_f32;
+	let max_length = max_length.min(MAX_RAY_LENGTH);
+	let max_steps = ray.max_steps(&**field);
+	while ray_steps < max_steps && ray_length <= max_length {
+		let distance = field.local_distance(ray_point);
+		if distance <= MIN_RAY_MARCH {
+			return true;
+		}
+		let ray_step = distance / field_scale;
+		ray_length += ray_step;
+		ray_point += ray_direction * ray_step;
+		ray_steps += 1;
+	}
+	false
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::nodes::field::tests::*;
+	use crate::nodes::field::FieldCommon;
+	use glam::{vec3, Quat};
+	use libstardustxr::flex_to_vec3;
+
+	#[test]
+	fn seeded_ray_march_skips_the_cleared_part_of_the_ray() {
+		let client = test_client();
+		// off the ray's axis so the march takes a few steps to close in on it
+		let sphere = create_sphere(&client, "ball", vec3(0_f32, 0.9_f32, 5_f32), 1_f32);
+		let field = sphere.field.get().unwrap();
+		let ray = || Ray {
+			origin: Vec3::ZERO,
+			direction: Vec3::Z,
+			space: get_reference_space_flex(&client, "/").unwrap(),
+			max_steps: None,
+		};
+
+		let fresh = ray_march(ray(), field);
+		assert!(fresh.distance <= MIN_RAY_MARCH);
+		assert!(fresh.clear_length > 0_f32);
+		let resumed = ray_march_from(ray(), field, fresh.clear_length);
+		assert!(resumed.distance <= MIN_RAY_MARCH);
+		assert!(resumed.clear_length >= fresh.clear_length);
+
+		// the sphere moving in front of the seed goes unnoticed by the resumed march
+		sphere
+			.spatial
+			.get()
+			.unwrap()
+			.set_local_transform(Mat4::from_translation(vec3(0_f32, 0_f32, 1.5_f32)));
+		assert!(ray_march(ray(), field).distance <= MIN_RAY_MARCH);
+		assert!(ray_march_from(ray(), field, fresh.clear_length).distance > MIN_RAY_MARCH);
+	}
+
+	#[test]
+	fn ray_lengths_are_multiples_of_the_ray_direction_in_scaled_spaces() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "shrunk", Vec3::ZERO, 1_f32);
+		sphere
+			.spatial
+			.get()
+			.unwrap()
+			.set_local_transform(Mat4::from_scale(Vec3::splat(0.5_f32)));
+		let field = sphere.field.get().unwrap();
+		let ray = |origin: Vec3, direction: Vec3| Ray {
+			origin,
+			direction,
+			space: get_reference_space_flex(&client, "/").unwrap(),
+			max_steps: None,
+		};
+
+		let exit = ray_march_exit(&ray(Vec3::ZERO, Vec3::X), field).unwrap();
+		assert_near(exit.point.x, 0.5_f32, 0.001_f32);
+		assert_near(exit.length, 0.5_f32, 0.001_f32);
+		let exit = ray_march_exit(&ray(Vec3::ZERO, Vec3::X * 2_f32), field).unwrap();
+		assert_near(exit.point.x, 0.5_f32, 0.001_f32);
+		assert_near(exit.length, 0.25_f32, 0.001_f32);
+
+		// steps sized in the field's space would overshoot the surface by double here
+		let outside = -Vec3::X * 5_f32;
+		assert!(!ray_blocked(&ray(outside, Vec3::X), field, 4.4_f32));
+		assert!(ray_blocked(&ray(outside, Vec3::X), field, 4.6_f32));
+		let result = ray_march(ray(outside, Vec3::X), field);
+		assert!(result.entered_interior);
+		assert!(result.clear_length <= 4.5_f32);
+		assert_near(
+			result.field_space_ray_length,
+			result.ray_length * 2_f32,
+			0.001_f32,
+		);
+	}
+
+	#[test]
+	fn ray_through_a_hollow_sphere_crosses_four_times() {
+		let client = test_client();
+		create_field(&client, "createCompoundField", "onion", Vec3::ZERO, |vec| {
+			vec.push("subtraction");
+			let mut children = vec.start_vector();
+			for radius in [1_f32, 0.5_f32] {
+				let mut child = children.start_vector();
+				child.push("sphere");
+				push_to_vec!(
+					&mut child,
+					mint::Vector3::from(Vec3::ZERO),
+					mint::Quaternion::from(Quat::IDENTITY),
+					radius
+				);
+				child.end_vector();
+			}
+			children.end_vector();
+		});
+		let onion = client.scenegraph.get_node("/field/onion").unwrap();
+		let ray = Ray {
+			origin: -Vec3::X * 5_f32,
+			direction: Vec3::X,
+			space: get_reference_space_flex(&client, "/").unwrap(),
+			max_steps: None,
+		};
+		let crossings = ray_march_all(&ray, onion.field.get().unwrap());
+		assert_eq!(crossings.len(), 4);
+		for (crossing, (x, entering)) in crossings.iter().zip([
+			(-1_f32, true),
+			(-0.5_f32, false),
+			(0.5_f32, true),
+			(1_f32, false),
+		]) {
+			assert_eq!(crossing.entering, entering);
+			assert_near(crossing.point.x, x, 0.002_f32);
+		}
+	}
+
+	#[test]
+	fn ray_step_budget_scales_with_complexity() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "budget_leaf", Vec3::Z * 10_f32, 0.5_f32);
+		// the same sphere at the end, with a row of spheres the ray only just misses on the way
+		let crowded = create_field(
+			&client,
+			"createCompoundField",
+			"budget_crowd",
+			Vec3::ZERO,
+			|vec| {
+				vec.push("union");
+				let mut children = vec.start_vector();
+				for z in 0..10 {
+					let mut child = children.start_vector();
+					child.push("sphere");
+					let position = if z == 0 {
+						Vec3::Z * 10_f32
+					} else {
+						vec3(0.6_f32, 0_f32, z as f32)
+					};
+					push_to_vec!(
+						&mut child,
+						mint::Vector3::from(position),
+						mint::Quaternion::from(Quat::IDENTITY),
+						0.5_f32
+					);
+					child.end_vector();
+				}
+				children.end_vector();
+			},
+		);
+		let ray = |max_steps: Option<u32>| Ray {
+			origin: Vec3::ZERO,
+			direction: Vec3::Z,
+			space: get_reference_space_flex(&client, "/").unwrap(),
+			max_steps,
+		};
+
+		let sphere_field = sphere.field.get().unwrap();
+		let simple = ray_march(ray(None), sphere_field);
+		assert!(simple.distance <= MIN_RAY_MARCH);
+		assert!(simple.ray_steps <= RAY_STEPS_PER_COMPLEXITY);
+
+		let deep_field = crowded.field.get().unwrap();
+		assert!(deep_field.ray_steps() > sphere_field.ray_steps());
+		let deep = ray_march(ray(None), deep_field);
+		assert!(deep.distance <= MIN_RAY_MARCH);
+		assert!(deep.ray_steps > simple.ray_steps);
+
+		// the client's override wins, even when it's too few to get there
+		let cut_short = ray_march(ray(Some(1)), deep_field);
+		assert_eq!(cut_short.ray_steps, 1);
+		assert!(cut_short.distance > MIN_RAY_MARCH);
+	}
+
+	#[test]
+	fn ray_exits_a_sphere_at_the_radius_minus_the_offset() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "exploding", Vec3::ZERO, 1_f32);
+		for offset in [0_f32, 0.3_f32, -0.3_f32] {
+			let exit = method(
+				&client,
+				sphere.get_path(),
+				"ray_march_exit",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push("/");
+					push_to_vec!(
+						vec,
+						mint::Vector3::from(vec3(offset, 0_f32, 0_f32)),
+						mint::Vector3::from(Vec3::X)
+					);
+				}),
+			)
+			.unwrap();
+			let exit = flexbuffers::Reader::get_root(exit.as_slice())
+				.unwrap()
+				.as_vector();
+			let point: Vec3 = flex_to_vec3!(exit.idx(0)).unwrap().into();
+			assert!(point.abs_diff_eq(Vec3::X, 0.002_f32), "{}", point);
+			assert_near(exit.idx(1).as_f32(), 1_f32 - offset, 0.002_f32);
+		}
+	}
+
+	#[test]
+	fn ray_through_a_sphere_diameter_is_inside_for_twice_the_radius() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "fog", Vec3::Z * 3_f32, 0.75_f32);
+		let interior_length = |origin: Vec3| {
+			read_f32(
+				&method(
+					&client,
+					sphere.get_path(),
+					"ray_interior_length",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push("/");
+						push_to_vec!(
+							vec,
+							mint::Vector3::from(origin),
+							mint::Vector3::from(Vec3::Z),
+							0_u32,
+							10_f32
+						);
+					}),
+				)
+				.unwrap(),
+			)
+		};
+		assert_near(interior_length(Vec3::ZERO), 1.5_f32, 0.005_f32);
+		// starting inside only counts from the origin on
+		assert_near(interior_length(Vec3::Z * 3_f32), 0.75_f32, 0.005_f32);
+		assert_near(interior_length(Vec3::X * 2_f32), 0_f32, 0.0001_f32);
+	}
+
+	/// Takes a fixed time per distance, so a march over it times out after a known number of steps.
+	struct SlowField {
+		space: Arc<Spatial>,
+		common: FieldCommon,
+	}
+	impl FieldTrait for SlowField {
+		fn local_distance(&self, p: Vec3A) -> f32 {
+			std::thread::sleep(MAX_RAY_MARCH_TIME / 2);
+			// inside a sphere so the steps stay tiny
+			p.length() - 100_f32
+		}
+		fn complexity(&self) -> u32 {
+			RAY_STEPS_PER_TIME_CHECK
+		}
+		fn spatial_ref(&self) -> &Spatial {
+			self.space.as_ref()
+		}
+		fn common(&self) -> &FieldCommon {
+			&self.common
+		}
+	}
+
+	#[test]
+	fn slow_field_march_times_out_within_the_budget() {
+		let client = test_client();
+		let node = Node::create(&client, "/", "slow", false).add_to_scenegraph();
+		let field = SlowField {
+			space: Spatial::add_to(&node, None, Mat4::IDENTITY).unwrap(),
+			common: Default::default(),
+		};
+		let ray = Ray {
+			origin: Vec3::ZERO,
+			direction: Vec3::Z,
+			space: field.space.clone(),
+			max_steps: Some(MAX_RAY_STEPS),
+		};
+
+		let result = ray_march_recording(ray, &field, 0_f32, None);
+		assert!(matches!(result.termination, MarchTermination::Timeout));
+		// a complex field checks the clock every step, so it stops as soon as it's over budget
+		assert!((1..=3).contains(&result.ray_steps), "{}", result.ray_steps);
+		// the part it did march is still reported
+		assert!(result.entered_interior);
+		assert!(result.distance < 0_f32);
+	}
+
+	#[test]
+	fn ray_blocked_only_before_the_target() {
+		let client = test_client();
+		let near = create_sphere(&client, "between", Vec3::Z * 5_f32, 1_f32);
+		let far = create_sphere(&client, "beyond", Vec3::Z * 20_f32, 1_f32);
+		let blocked = |node: &Node, target: f32| {
+			let blocked = method(
+				&client,
+				node.get_path(),
+				"ray_blocked",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push("/");
+					push_to_vec!(
+						vec,
+						mint::Vector3::from(Vec3::ZERO),
+						mint::Vector3::from(Vec3::Z)
+					);
+					vec.push(0_u32);
+					vec.push(target);
+				}),
+			)
+			.unwrap();
+			flexbuffers::Reader::get_root(blocked.as_slice())
+				.unwrap()
+				.as_bool()
+		};
+		assert!(blocked(&near, 10_f32));
+		assert!(!blocked(&near, 3_f32));
+		assert!(!blocked(&far, 10_f32));
+	}
+
+	#[test]
+	fn surface_point_along_lands_on_top_of_a_sphere() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "globe", Vec3::ZERO, 1_f32);
+		let drop_from = |origin: Vec3| {
+			read_vec3s(
+				&method(
+					&client,
+					sphere.get_path(),
+					"surface_point_along",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push("/");
+						push_to_vec!(
+							vec,
+							mint::Vector3::from(origin),
+							mint::Vector3::from(-Vec3::Y)
+						);
+					}),
+				)
+				.unwrap(),
+			)
+		};
+		let landing = drop_from(vec3(0.3_f32, 5_f32, 0.2_f32));
+		assert_eq!(landing.len(), 1);
+		let expected = vec3(0.3_f32, (1_f32 - 0.13_f32).sqrt(), 0.2_f32);
+		assert!(
+			landing[0].abs_diff_eq(expected, 0.002_f32),
+			"{}",
+			landing[0]
+		);
+		// straight down past the side of the sphere never lands
+		assert!(drop_from(vec3(3_f32, 5_f32, 0_f32)).is_empty());
+	}
+
+	#[test]
+	fn ray_march_trace_ends_where_the_march_does() {
+		let client = test_client();
+		let sphere = create_sphere(&client, "traced", vec3(0.3_f32, 0.2_f32, 4_f32), 1_f32);
+		let field = sphere.field.get().unwrap();
+		let ray = || Ray {
+			origin: Vec3::ZERO,
+			direction: Vec3::Z,
+			space: get_reference_space_flex(&client, "/").unwrap(),
+			max_steps: Some(50),
+		};
+
+		let plain = ray_march(ray(), field);
+		let (traced, trace) = ray_march_trace(ray(), field);
+		assert_eq!(trace.len() as u32, plain.ray_steps);
+		assert_eq!(traced.ray_steps, plain.ray_steps);
+		assert_eq!(traced.ray_length, plain.ray_length);
+		assert_eq!(traced.distance, plain.distance);
+
+		// the sphere's space is only translated, so field space steps line up with the ray
+		let last = trace.last().unwrap();
+		let end = last.point + (Vec3::Z * last.march_distance);
+		let march_end = Vec3::Z * plain.ray_length
+			- sphere
+				.spatial
+				.get()
+				.unwrap()
+				.global_transform()
+				.w_axis
+				.truncate();
+		assert!(
+			end.abs_diff_eq(march_end, 0.0001_f32),
+			"{} vs {}",
+			end,
+			march_end
+		);
+		assert_eq!(
+			trace.first().unwrap().distance,
+			distance_at(&client, &sphere, Vec3::ZERO)
+		);
+	}
+
+	#[test]
+	fn march_through_a_thin_box_enters_its_interior() {
+		let client = test_client();
+		let sheet = create_box(
+			&client,
+			"sheet",
+			Vec3::Z * 5_f32,
+			vec3(1_f32, 1_f32, 0.0015_f32),
+		);
+		let field = sheet.field.get().unwrap();
+		let ray = |origin: Vec3| Ray {
+			origin,
+			direction: Vec3::Z,
+			space: get_reference_space_flex(&client, "/").unwrap(),
+			max_steps: None,
+		};
+
+		let through = ray_march(ray(vec3(0.2_f32, -0.1_f32, 0_f32)), field);
+		assert!(through.entered_interior);
+		assert!(through.ray_length > 5_f32);
+		// grazing past the edge gets close without ever being inside
+		let past = ray_march(ray(vec3(0.5005_f32, 0_f32, 0_f32)), field);
+		assert!(past.distance < 0.001_f32);
+		assert!(!past.entered_interior);
+	}
+
+	#[test]
+	fn vertical_gap_over_a_tilted_plane() {
+		let client = test_client();
+		let tilt = std::f32::consts::FRAC_PI_6;
+		let ramp = create_box(&client, "ramp", Vec3::ZERO, vec3(20_f32, 1_f32, 20_f32));
+		ramp.spatial
+			.get()
+			.unwrap()
+			.set_local_transform(Mat4::from_rotation_z(tilt));
+		let vertical_gap = |point: Vec3| {
+			let gap = method(&client, ramp.get_path(), "vertical_gap", &point_args(point)).unwrap();
+			flexbuffers::Reader::get_root(gap.as_slice())
+				.unwrap()
+				.as_vector()
+				.idx(0)
+				.as_f32()
+		};
+		let point = Vec3::Y * 3_f32;
+		let distance = distance_at(&client, &ramp, point);
+		assert_near(distance, (3_f32 * tilt.cos()) - 0.5_f32, 0.0001_f32);
+		// straight down is longer than the perpendicular by 1 / cos of the slope
+		assert_near(vertical_gap(point), distance / tilt.cos(), 0.002_f32);
+		assert!(vertical_gap(point) > distance + 0.1_f32);
+	}
+}
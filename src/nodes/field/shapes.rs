@@ -0,0 +1,3122 @@
+use super::{
+	flex_to_finite_vec3, flex_to_size, flex_to_size_vec3, get_reference_space_flex, DistanceCache,
+	DistanceMetric, Field, FieldCommon, FieldFeature, FieldTrait, FIELD_PARENT_PATH,
+	FIELD_REGISTRY, MIN_FIELD_SIZE,
+};
+use crate::core::client::Client;
+use crate::nodes::core::{check_flex_args, flex_args, Node};
+use crate::nodes::spatial::{get_spatial_parent_flex, get_transform_pose_flex, Spatial};
+use anyhow::{anyhow, ensure, Result};
+use glam::{swizzles::*, vec2, vec3, vec3a, Mat4, Quat, Vec2, Vec3, Vec3A};
+use libstardustxr::flex::flexbuffer_from_vector_arguments;
+use libstardustxr::{flex_to_quat, flex_to_vec3};
+use parking_lot::Mutex;
+use portable_atomic::AtomicF32;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+/// The axis along the largest of `size`, or `None` if it's tied with another.
+pub(super) fn dominant_axis(size: Vec3) -> Option<Vec3A> {
+	let size = size.abs();
+	let largest = size.max_element();
+	let axes = [Vec3A::X, Vec3A::Y, Vec3A::Z];
+	let mut dominant = axes
+		.iter()
+		.zip(size.to_array())
+		.filter(|(_, extent)| *extent == largest);
+	match (dominant.next(), dominant.next()) {
+		(Some((axis, _)), None) => Some(*axis),
+		_ => None,
+	}
+}
+
+pub(super) fn box_distance(p: Vec3A, size: Vec3) -> f32 {
+	box_distance_metric(p, size, DistanceMetric::Euclidean)
+}
+fn box_distance_metric(p: Vec3A, size: Vec3, metric: DistanceMetric) -> f32 {
+	let q = vec3(
+		p.x.abs() - (size.x * 0.5_f32),
+		p.y.abs() - (size.y * 0.5_f32),
+		p.z.abs() - (size.z * 0.5_f32),
+	);
+	let v = vec3a(q.x.max(0_f32), q.y.max(0_f32), q.z.max(0_f32));
+	let outside_distance = match metric {
+		DistanceMetric::Euclidean => v.length(),
+		DistanceMetric::Chebyshev => v.max_element(),
+		DistanceMetric::Manhattan => v.x + v.y + v.z,
+	};
+	outside_distance + q.x.max(q.y.max(q.z)).min(0_f32)
+}
+
+pub struct BoxField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) size: Mutex<Vec3>,
+}
+
+impl BoxField {
+	pub fn add_to(node: &Arc<Node>, size: Vec3) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let box_field = BoxField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			size: Mutex::new(size),
+		};
+		box_field.add_field_methods(node);
+		add_field_signal!(node, "setSize", BoxField::set_size_flex);
+		let _ = node.field.set(FIELD_REGISTRY.add(Field::Box(box_field)));
+		Ok(())
+	}
+
+	pub fn set_size(&self, size: Vec3) {
+		*self.size.lock() = size;
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_size_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let root = flexbuffers::Reader::get_root(data)?;
+		let size = flex_to_size_vec3(root, "Size")?;
+		if let Field::Box(box_field) = node.field.get().unwrap().as_ref() {
+			box_field.set_size(size.into());
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for BoxField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		box_distance(p, *self.size.lock())
+	}
+	fn local_distance_metric(&self, p: Vec3A, metric: DistanceMetric) -> f32 {
+		let _stats = self.common.stats.record();
+		box_distance_metric(p, *self.size.lock(), metric)
+	}
+	fn local_closest_point(&self, p: Vec3A, r: f32) -> Vec3A {
+		self.local_closest_feature(p, r).1
+	}
+	fn local_closest_feature(&self, p: Vec3A, _r: f32) -> (FieldFeature, Vec3A) {
+		let half_size = Vec3A::from(*self.size.lock()) * 0.5_f32;
+		let outside = p.abs().cmpgt(half_size);
+		let closest_point = if outside.any() {
+			p.clamp(-half_size, half_size)
+		} else {
+			// inside, so push out through the nearest face
+			let gaps = half_size - p.abs();
+			let mut closest_point = p;
+			let axis = if gaps.x <= gaps.y && gaps.x <= gaps.z {
+				0
+			} else if gaps.y <= gaps.z {
+				1
+			} else {
+				2
+			};
+			closest_point[axis] = half_size[axis].copysign(p[axis]);
+			closest_point
+		};
+		let feature = match outside.bitmask().count_ones() {
+			0 | 1 => FieldFeature::Face,
+			2 => FieldFeature::Edge,
+			_ => FieldFeature::Vertex,
+		};
+		(feature, closest_point)
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		self.size.lock().length() * 0.5_f32
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+/// Rounding for each axis, or the same on all of them if it's just a number.
+fn flex_to_rounding<B: flexbuffers::Buffer>(flex: flexbuffers::Reader<B>) -> Result<Vec3> {
+	let rounding: Vec3 = match flex_to_vec3!(flex.clone()) {
+		Some(rounding) => rounding.into(),
+		None => Vec3::splat(flex.as_f32()),
+	};
+	ensure!(rounding.is_finite(), "Rounding has a non-finite component");
+	Ok(rounding)
+}
+
+pub struct RoundedBoxField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	/// Size and rounding radius along each axis.
+	pub(super) size: Mutex<(Vec3, Vec3)>,
+}
+
+impl RoundedBoxField {
+	/// A box whose edges are rounded by `rounding`, which can differ per axis to give elliptical corners.
+	pub fn add_to(node: &Arc<Node>, size: Vec3, rounding: Vec3) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let rounded_box_field = RoundedBoxField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			size: Mutex::new((size, rounding)),
+		};
+		rounded_box_field.add_field_methods(node);
+		add_field_signal!(node, "setSize", RoundedBoxField::set_size_flex);
+		add_field_signal!(node, "setRounding", RoundedBoxField::set_rounding_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::RoundedBox(rounded_box_field)));
+		Ok(())
+	}
+
+	pub fn set_size(&self, size: Vec3) {
+		self.size.lock().0 = size;
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_rounding(&self, rounding: Vec3) {
+		self.size.lock().1 = rounding;
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_size_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let root = flexbuffers::Reader::get_root(data)?;
+		let size = flex_to_size_vec3(root, "Size")?;
+		if let Field::RoundedBox(rounded_box_field) = node.field.get().unwrap().as_ref() {
+			rounded_box_field.set_size(size.into());
+		}
+		Ok(())
+	}
+
+	pub fn set_rounding_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let rounding = flex_to_rounding(flexbuffers::Reader::get_root(data)?)?;
+		if let Field::RoundedBox(rounded_box_field) = node.field.get().unwrap().as_ref() {
+			rounded_box_field.set_rounding(rounding);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for RoundedBoxField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let (size, rounding) = *self.size.lock();
+		let half_size = Vec3A::from(size) * 0.5_f32;
+		let rounding = Vec3A::from(rounding)
+			.min(half_size)
+			.max(Vec3A::splat(MIN_FIELD_SIZE));
+		// past the unrounded core in any axis means being in the rounded part,
+		// which is an ellipsoid around the core's closest corner/edge/face
+		let q = (p.abs() - half_size + rounding).max(Vec3A::ZERO);
+		if q == Vec3A::ZERO {
+			return (p.abs() - half_size).max_element();
+		}
+		let k0 = (q / rounding).length();
+		let k1 = (q / (rounding * rounding)).length();
+		k0 * (k0 - 1_f32) / k1
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		self.size.lock().0.length() * 0.5_f32
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+pub struct QuadField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	/// Width, height and edge radius.
+	pub(super) size: Mutex<(f32, f32, f32)>,
+}
+
+impl QuadField {
+	/// A flat `width` by `height` rectangle in the XY plane, thickened by `radius` all around
+	/// so its edges are rounded and it's `radius * 2` thick along Z.
+	pub fn add_to(node: &Arc<Node>, width: f32, height: f32, radius: f32) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let quad_field = QuadField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			size: Mutex::new((width, height, radius)),
+		};
+		quad_field.add_field_methods(node);
+		add_field_signal!(node, "setSize", QuadField::set_size_flex);
+		let _ = node.field.set(FIELD_REGISTRY.add(Field::Quad(quad_field)));
+		Ok(())
+	}
+
+	pub fn set_size(&self, width: f32, height: f32, radius: f32) {
+		*self.size.lock() = (width, height, radius);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_size_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let flex_vec = flex_args(data, &["width", "height", "radius"])?;
+		let width = flex_to_size(flex_vec.idx(0), "Width")?;
+		let height = flex_to_size(flex_vec.idx(1), "Height")?;
+		let radius = flex_to_size(flex_vec.idx(2), "Radius")?;
+		if let Field::Quad(quad_field) = node.field.get().unwrap().as_ref() {
+			quad_field.set_size(width, height, radius);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for QuadField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let (width, height, radius) = *self.size.lock();
+		box_distance(p, vec3(width, height, 0_f32)) - radius
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		let (width, height, radius) = *self.size.lock();
+		(vec2(width, height).length() * 0.5_f32) + radius
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+fn cylinder_distance(p: Vec3A, length: f32, radius: f32) -> f32 {
+	let d = vec2(p.xy().length() - radius, p.z.abs() - (length * 0.5));
+
+	d.x.max(d.y).min(0_f32) + d.max(Vec2::ZERO).length()
+}
+
+/// Checks `cylinder_distance` against the expected distances just past the tip of the axis and
+/// just past the edge of a cylinder with a different length and radius, where mixing them up shows.
+/// Returns whether both matched, then the measured and expected distances for the tip and the edge.
+pub fn cylinder_self_test() -> (bool, [(f32, f32); 2]) {
+	check_cylinder_distance(cylinder_distance)
+}
+fn check_cylinder_distance(distance: fn(Vec3A, f32, f32) -> f32) -> (bool, [(f32, f32); 2]) {
+	let (length, radius, offset) = (2_f32, 0.5_f32, 0.1_f32);
+	let tip = (
+		distance(vec3a(0_f32, 0_f32, (length * 0.5) + offset), length, radius),
+		offset,
+	);
+	let edge = (
+		distance(vec3a(radius + offset, 0_f32, 0_f32), length, radius),
+		offset,
+	);
+	let passed = [tip, edge]
+		.iter()
+		.all(|(measured, expected)| (measured - expected).abs() < 0.0001_f32);
+	(passed, [tip, edge])
+}
+
+pub fn cylinder_self_test_flex(
+	_node: &Node,
+	_calling_client: Arc<Client>,
+	_data: &[u8],
+) -> Result<Vec<u8>> {
+	let (passed, [tip, edge]) = cylinder_self_test();
+	Ok(flexbuffer_from_vector_arguments(|vec| {
+		vec.push(passed);
+		for (measured, expected) in [tip, edge] {
+			vec.push(measured);
+			vec.push(expected);
+		}
+	}))
+}
+
+pub struct CylinderField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	/// Length and radius, locked together so a resize is never seen half done.
+	pub(super) size: Mutex<(f32, f32)>,
+}
+
+impl CylinderField {
+	pub fn add_to(node: &Arc<Node>, length: f32, radius: f32) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let cylinder_field = CylinderField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			size: Mutex::new((length, radius)),
+		};
+		cylinder_field.add_field_methods(node);
+		add_field_signal!(node, "setSize", CylinderField::set_size_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::Cylinder(cylinder_field)));
+		Ok(())
+	}
+
+	pub fn set_size(&self, length: f32, radius: f32) {
+		*self.size.lock() = (length, radius);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_size_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let flex_vec = flex_args(data, &["length", "radius"])?;
+		let length = flex_to_size(flex_vec.idx(0), "Length")?;
+		let radius = flex_to_size(flex_vec.idx(1), "Radius")?;
+		if let Field::Cylinder(cylinder_field) = node.field.get().unwrap().as_ref() {
+			cylinder_field.set_size(length, radius);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for CylinderField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let (length, radius) = *self.size.lock();
+		cylinder_distance(p, length, radius)
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		let (length, radius) = *self.size.lock();
+		vec2(radius, length * 0.5_f32).length()
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+pub struct SphereField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) radius: AtomicF32,
+}
+
+impl SphereField {
+	pub fn add_to(node: &Arc<Node>, radius: f32) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let sphere_field = SphereField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			radius: AtomicF32::new(radius),
+		};
+		sphere_field.add_field_methods(node);
+		add_field_signal!(node, "setRadius", SphereField::set_radius_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::Sphere(sphere_field)));
+		Ok(())
+	}
+
+	pub fn set_radius(&self, radius: f32) {
+		self.radius.store(radius, Ordering::Relaxed);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_radius_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let root = flexbuffers::Reader::get_root(data)?;
+		if let Field::Sphere(sphere_field) = node.field.get().unwrap().as_ref() {
+			sphere_field.set_radius(flex_to_size(root, "Radius")?);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for SphereField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		p.length() - self.radius.load(Ordering::Relaxed)
+	}
+	fn local_normal(&self, p: Vec3A, _r: f32) -> Vec3A {
+		p.normalize()
+	}
+	fn local_closest_point(&self, p: Vec3A, _r: f32) -> Vec3A {
+		p.normalize() * self.radius.load(Ordering::Relaxed)
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		self.radius.load(Ordering::Relaxed)
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+pub struct ConeField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) angle: AtomicF32,
+	pub(super) length: AtomicF32,
+}
+
+impl ConeField {
+	/// A cone with its apex at the origin opening towards +Z, `angle` being the half-angle in radians.
+	pub fn add_to(node: &Arc<Node>, angle: f32, length: f32) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let cone_field = ConeField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			angle: AtomicF32::new(angle),
+			length: AtomicF32::new(length),
+		};
+		cone_field.add_field_methods(node);
+		add_field_signal!(node, "setSize", ConeField::set_size_flex);
+		let _ = node.field.set(FIELD_REGISTRY.add(Field::Cone(cone_field)));
+		Ok(())
+	}
+
+	pub fn set_size(&self, angle: f32, length: f32) {
+		self.angle.store(angle, Ordering::Relaxed);
+		self.length.store(length, Ordering::Relaxed);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_size_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let flex_vec = flex_args(data, &["angle", "length"])?;
+		let angle = flex_vec.idx(0).as_f32();
+		let length = flex_to_size(flex_vec.idx(1), "Length")?;
+		if let Field::Cone(cone_field) = node.field.get().unwrap().as_ref() {
+			cone_field.set_size(angle, length);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for ConeField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let angle = self.angle.load(Ordering::Relaxed).max(MIN_FIELD_SIZE);
+		let length = self.length.load(Ordering::Relaxed).max(MIN_FIELD_SIZE);
+		// point on the base rim in the 2D (radial, -z) plane
+		let q = vec2(angle.tan(), -1_f32) * length;
+		let w = vec2(p.xy().length(), -p.z);
+		let a = w - (q * (w.dot(q) / q.dot(q)).clamp(0_f32, 1_f32));
+		let b = w - (q * vec2((w.x / q.x).clamp(0_f32, 1_f32), 1_f32));
+		let k = q.y.signum();
+		let d = a.dot(a).min(b.dot(b));
+		let s = (k * ((w.x * q.y) - (w.y * q.x))).max(k * (w.y - q.y));
+		d.sqrt() * s.signum()
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		let angle = self.angle.load(Ordering::Relaxed);
+		let length = self.length.load(Ordering::Relaxed);
+		vec2(length * angle.tan(), length).length()
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+pub struct CappedTorusField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) major_radius: AtomicF32,
+	pub(super) minor_radius: AtomicF32,
+	pub(super) angle: AtomicF32,
+}
+
+impl CappedTorusField {
+	/// An arc of a torus lying on the XY plane, centered on +Y and spanning `angle` radians either side of it.
+	pub fn add_to(
+		node: &Arc<Node>,
+		major_radius: f32,
+		minor_radius: f32,
+		angle: f32,
+	) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let capped_torus_field = CappedTorusField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			major_radius: AtomicF32::new(major_radius),
+			minor_radius: AtomicF32::new(minor_radius),
+			angle: AtomicF32::new(angle),
+		};
+		capped_torus_field.add_field_methods(node);
+		add_field_signal!(node, "setSize", CappedTorusField::set_size_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::CappedTorus(capped_torus_field)));
+		Ok(())
+	}
+
+	pub fn set_size(&self, major_radius: f32, minor_radius: f32, angle: f32) {
+		self.major_radius.store(major_radius, Ordering::Relaxed);
+		self.minor_radius.store(minor_radius, Ordering::Relaxed);
+		self.angle.store(angle, Ordering::Relaxed);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_size_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let flex_vec = flex_args(data, &["major radius", "minor radius", "angle"])?;
+		let major_radius = flex_to_size(flex_vec.idx(0), "Major radius")?;
+		let minor_radius = flex_to_size(flex_vec.idx(1), "Minor radius")?;
+		let angle = flex_vec.idx(2).as_f32();
+		if let Field::CappedTorus(capped_torus_field) = node.field.get().unwrap().as_ref() {
+			capped_torus_field.set_size(major_radius, minor_radius, angle);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for CappedTorusField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let major_radius = self.major_radius.load(Ordering::Relaxed);
+		let minor_radius = self.minor_radius.load(Ordering::Relaxed);
+		let angle = self.angle.load(Ordering::Relaxed);
+		let sc = vec2(angle.sin(), angle.cos());
+		let p = vec3a(p.x.abs(), p.y, p.z);
+		let k = if sc.y * p.x > sc.x * p.y {
+			p.xy().dot(sc)
+		} else {
+			p.xy().length()
+		};
+		(p.length_squared() + (major_radius * major_radius) - (2_f32 * major_radius * k)).sqrt()
+			- minor_radius
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		self.major_radius.load(Ordering::Relaxed) + self.minor_radius.load(Ordering::Relaxed)
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+pub struct TaperedCapsuleField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	/// Bottom radius, top radius and length.
+	pub(super) size: Mutex<(f32, f32, f32)>,
+}
+
+impl TaperedCapsuleField {
+	/// A rounded cone from a sphere of `bottom_radius` at the origin to one of `top_radius` at `length` along +Y.
+	pub fn add_to(
+		node: &Arc<Node>,
+		bottom_radius: f32,
+		top_radius: f32,
+		length: f32,
+	) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let tapered_capsule_field = TaperedCapsuleField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			size: Mutex::new((bottom_radius, top_radius, length)),
+		};
+		tapered_capsule_field.add_field_methods(node);
+		add_field_signal!(node, "setSize", TaperedCapsuleField::set_size_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::TaperedCapsule(tapered_capsule_field)));
+		Ok(())
+	}
+
+	pub fn set_size(&self, bottom_radius: f32, top_radius: f32, length: f32) {
+		*self.size.lock() = (bottom_radius, top_radius, length);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_size_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let flex_vec = flex_args(data, &["bottom radius", "top radius", "length"])?;
+		let bottom_radius = flex_to_size(flex_vec.idx(0), "Bottom radius")?;
+		let top_radius = flex_to_size(flex_vec.idx(1), "Top radius")?;
+		let length = flex_to_size(flex_vec.idx(2), "Length")?;
+		if let Field::TaperedCapsule(tapered_capsule_field) = node.field.get().unwrap().as_ref() {
+			tapered_capsule_field.set_size(bottom_radius, top_radius, length);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for TaperedCapsuleField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let (bottom_radius, top_radius, length) = *self.size.lock();
+		let length = length.max(MIN_FIELD_SIZE);
+		let b = ((bottom_radius - top_radius) / length).clamp(-1_f32, 1_f32);
+		let a = (1_f32 - (b * b)).sqrt();
+		let q = vec2(p.xz().length(), p.y);
+		let k = q.dot(vec2(-b, a));
+		if k < 0_f32 {
+			q.length() - bottom_radius
+		} else if k > a * length {
+			(q - vec2(0_f32, length)).length() - top_radius
+		} else {
+			q.dot(vec2(a, b)) - bottom_radius
+		}
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		let (bottom_radius, top_radius, length) = *self.size.lock();
+		bottom_radius.max(length + top_radius)
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+pub struct PyramidField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	/// Base half-width and height.
+	pub(super) size: Mutex<(f32, f32)>,
+}
+
+impl PyramidField {
+	/// A square based pyramid with its base centered on the origin and its apex at `height` along +Y.
+	pub fn add_to(node: &Arc<Node>, half_width: f32, height: f32) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let pyramid_field = PyramidField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			size: Mutex::new((half_width, height)),
+		};
+		pyramid_field.add_field_methods(node);
+		add_field_signal!(node, "setSize", PyramidField::set_size_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::Pyramid(pyramid_field)));
+		Ok(())
+	}
+
+	pub fn set_size(&self, half_width: f32, height: f32) {
+		*self.size.lock() = (half_width, height);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_size_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let flex_vec = flex_args(data, &["half width", "height"])?;
+		let half_width = flex_to_size(flex_vec.idx(0), "Half width")?;
+		let height = flex_to_size(flex_vec.idx(1), "Height")?;
+		if let Field::Pyramid(pyramid_field) = node.field.get().unwrap().as_ref() {
+			pyramid_field.set_size(half_width, height);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for PyramidField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let (half_width, height) = *self.size.lock();
+		// the pyramid SDF is for a base of width 1, so scale everything to that and back
+		let base_width = (half_width * 2_f32).max(MIN_FIELD_SIZE);
+		let h = height / base_width;
+		let p = p / base_width;
+		if p.y <= 0_f32 {
+			// below the base, so it's the distance to that square
+			let q = vec3(p.x.abs() - 0.5_f32, -p.y, p.z.abs() - 0.5_f32);
+			return q.max(Vec3::ZERO).length() * base_width;
+		}
+
+		let m2 = (h * h) + 0.25_f32;
+		let (x, z) = (p.x.abs(), p.z.abs());
+		let (x, z) = if z > x { (z, x) } else { (x, z) };
+		let (x, z) = (x - 0.5_f32, z - 0.5_f32);
+		let q = vec3(z, (h * p.y) - (0.5_f32 * x), (h * x) + (0.5_f32 * p.y));
+		let s = (-q.x).max(0_f32);
+		let t = ((q.y - (0.5_f32 * z)) / (m2 + 0.25_f32)).clamp(0_f32, 1_f32);
+		let a = (m2 * (q.x + s) * (q.x + s)) + (q.y * q.y);
+		let b = (m2 * (q.x + (0.5_f32 * t)) * (q.x + (0.5_f32 * t)))
+			+ ((q.y - (m2 * t)) * (q.y - (m2 * t)));
+		let d2 = if q.y.min((-q.x * m2) - (q.y * 0.5_f32)) > 0_f32 {
+			0_f32
+		} else {
+			a.min(b)
+		};
+		let d = ((d2 + (q.z * q.z)) / m2).sqrt();
+		// that's only the distance to the sloped faces, so inside the base may be closer
+		let d = if q.z > 0_f32 { d } else { (-d).max(-p.y) };
+		d * base_width
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		let (half_width, height) = *self.size.lock();
+		(half_width * std::f32::consts::SQRT_2).max(height)
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+/// A chain of capsules through `points`, like an arm made of bones.
+pub struct PolylineCapsuleField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) points: Vec<Vec3A>,
+	pub(super) radius: AtomicF32,
+}
+
+impl PolylineCapsuleField {
+	pub fn add_to(node: &Arc<Node>, points: Vec<Vec3A>, radius: f32) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		ensure!(!points.is_empty(), "Polyline capsule field has no points");
+		let polyline_capsule_field = PolylineCapsuleField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			points,
+			radius: AtomicF32::new(radius),
+		};
+		polyline_capsule_field.add_field_methods(node);
+		add_field_signal!(node, "setRadius", PolylineCapsuleField::set_radius_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::PolylineCapsule(polyline_capsule_field)));
+		Ok(())
+	}
+
+	pub fn set_radius(&self, radius: f32) {
+		self.radius.store(radius, Ordering::Relaxed);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_radius_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let root = flexbuffers::Reader::get_root(data)?;
+		if let Field::PolylineCapsule(polyline_capsule_field) = node.field.get().unwrap().as_ref() {
+			polyline_capsule_field.set_radius(flex_to_size(root, "Radius")?);
+		}
+		Ok(())
+	}
+
+	fn closest_point_on_polyline(&self, p: Vec3A) -> Vec3A {
+		if self.points.len() == 1 {
+			return self.points[0];
+		}
+		self.points
+			.windows(2)
+			.map(|segment| {
+				let (a, b) = (segment[0], segment[1]);
+				let ab = b - a;
+				let length_squared = ab.length_squared();
+				if length_squared == 0_f32 {
+					return a;
+				}
+				a + (ab * ((p - a).dot(ab) / length_squared).clamp(0_f32, 1_f32))
+			})
+			.min_by(|point_a, point_b| {
+				point_a
+					.distance_squared(p)
+					.total_cmp(&point_b.distance_squared(p))
+			})
+			.unwrap()
+	}
+}
+
+impl FieldTrait for PolylineCapsuleField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		self.closest_point_on_polyline(p).distance(p) - self.radius.load(Ordering::Relaxed)
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		self.points
+			.iter()
+			.map(|point| point.length())
+			.fold(0_f32, f32::max)
+			+ self.radius.load(Ordering::Relaxed)
+	}
+	fn complexity(&self) -> u32 {
+		self.points.len() as u32
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+pub struct WedgeField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	/// Start angle, end angle, radius and height.
+	pub(super) size: Mutex<(f32, f32, f32, f32)>,
+}
+
+impl WedgeField {
+	/// A pie slice in the XZ plane from `start_angle` to `end_angle` (radians from +X towards +Z),
+	/// extruded `height` along Y and centered on the origin.
+	pub fn add_to(
+		node: &Arc<Node>,
+		start_angle: f32,
+		end_angle: f32,
+		radius: f32,
+		height: f32,
+	) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let wedge_field = WedgeField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			size: Mutex::new((start_angle, end_angle, radius, height)),
+		};
+		wedge_field.add_field_methods(node);
+		add_field_signal!(node, "setSize", WedgeField::set_size_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::Wedge(wedge_field)));
+		Ok(())
+	}
+
+	pub fn set_size(&self, start_angle: f32, end_angle: f32, radius: f32, height: f32) {
+		*self.size.lock() = (start_angle, end_angle, radius, height);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_size_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let flex_vec = flex_args(data, &["start angle", "end angle", "radius", "height"])?;
+		let start_angle = flex_vec.idx(0).as_f32();
+		let end_angle = flex_vec.idx(1).as_f32();
+		let radius = flex_to_size(flex_vec.idx(2), "Radius")?;
+		let height = flex_to_size(flex_vec.idx(3), "Height")?;
+		if let Field::Wedge(wedge_field) = node.field.get().unwrap().as_ref() {
+			wedge_field.set_size(start_angle, end_angle, radius, height);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for WedgeField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let (start_angle, end_angle, radius, height) = *self.size.lock();
+		// rotate the slice so it's symmetric around the second axis, then it's the 2D pie SDF
+		let middle_angle = (start_angle + end_angle) * 0.5_f32;
+		let half_angle = ((end_angle - start_angle) * 0.5_f32).clamp(0_f32, std::f32::consts::PI);
+		let (sin_middle, cos_middle) = middle_angle.sin_cos();
+		let q = vec2(
+			((p.z * cos_middle) - (p.x * sin_middle)).abs(),
+			(p.x * cos_middle) + (p.z * sin_middle),
+		);
+		let (sin_half, cos_half) = half_angle.sin_cos();
+		let c = vec2(sin_half, cos_half);
+		let l = q.length() - radius;
+		let m = (q - (c * q.dot(c).clamp(0_f32, radius))).length();
+		let sector_distance = l.max(m * ((c.y * q.x) - (c.x * q.y)).signum());
+
+		let d = vec2(sector_distance, p.y.abs() - (height * 0.5_f32));
+		d.x.max(d.y).min(0_f32) + d.max(Vec2::ZERO).length()
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		let (_, _, radius, height) = *self.size.lock();
+		vec2(radius, height * 0.5_f32).length()
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+pub struct OctahedronField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) size: AtomicF32,
+}
+
+impl OctahedronField {
+	/// A regular octahedron with its vertices `size` along each axis.
+	pub fn add_to(node: &Arc<Node>, size: f32) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let octahedron_field = OctahedronField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			size: AtomicF32::new(size),
+		};
+		octahedron_field.add_field_methods(node);
+		add_field_signal!(node, "setSize", OctahedronField::set_size_flex);
+		let _ = node
+			.field
+			.set(FIELD_REGISTRY.add(Field::Octahedron(octahedron_field)));
+		Ok(())
+	}
+
+	pub fn set_size(&self, size: f32) {
+		self.size.store(size, Ordering::Relaxed);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_size_flex(node: &Node, _calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+		let root = flexbuffers::Reader::get_root(data)?;
+		if let Field::Octahedron(octahedron_field) = node.field.get().unwrap().as_ref() {
+			octahedron_field.set_size(flex_to_size(root, "Size")?);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for OctahedronField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let size = self.size.load(Ordering::Relaxed);
+		let p = p.abs();
+		let m = p.x + p.y + p.z - size;
+		// rotate the components so the closest face is the one facing x
+		let q = if 3_f32 * p.x < m {
+			p
+		} else if 3_f32 * p.y < m {
+			p.yzx()
+		} else if 3_f32 * p.z < m {
+			p.zxy()
+		} else {
+			return m * 0.577_350_27_f32;
+		};
+		let k = (0.5_f32 * (q.z - q.y + size)).clamp(0_f32, size);
+		vec3a(q.x, q.y - size + k, q.z - k).length()
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		self.size.load(Ordering::Relaxed)
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+/// The region between two parallel planes either side of the local XZ plane, infinite along X and Z.
+pub struct SlabField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	pub(super) thickness: AtomicF32,
+}
+
+impl SlabField {
+	pub fn add_to(node: &Arc<Node>, thickness: f32) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		let slab_field = SlabField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			thickness: AtomicF32::new(thickness),
+		};
+		slab_field.add_field_methods(node);
+		add_field_signal!(node, "setThickness", SlabField::set_thickness_flex);
+		let _ = node.field.set(FIELD_REGISTRY.add(Field::Slab(slab_field)));
+		Ok(())
+	}
+
+	pub fn set_thickness(&self, thickness: f32) {
+		self.thickness.store(thickness, Ordering::Relaxed);
+		DistanceCache::invalidate_all();
+	}
+
+	pub fn set_thickness_flex(
+		node: &Node,
+		_calling_client: Arc<Client>,
+		data: &[u8],
+	) -> Result<()> {
+		let root = flexbuffers::Reader::get_root(data)?;
+		if let Field::Slab(slab_field) = node.field.get().unwrap().as_ref() {
+			slab_field.set_thickness(flex_to_size(root, "Thickness")?);
+		}
+		Ok(())
+	}
+}
+
+impl FieldTrait for SlabField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		p.y.abs() - (self.thickness.load(Ordering::Relaxed) * 0.5_f32)
+	}
+	fn local_normal(&self, p: Vec3A, _r: f32) -> Vec3A {
+		Vec3A::Y * p.y.signum()
+	}
+	fn local_closest_point(&self, p: Vec3A, _r: f32) -> Vec3A {
+		vec3a(
+			p.x,
+			p.y.signum() * self.thickness.load(Ordering::Relaxed) * 0.5_f32,
+			p.z,
+		)
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+/// Barycentric coordinates of the closest point to `p` on the triangle `a`, `b`, `c`.
+fn closest_point_on_triangle(p: Vec3A, a: Vec3A, b: Vec3A, c: Vec3A) -> Vec3A {
+	let ab = b - a;
+	let ac = c - a;
+	let ap = p - a;
+	let d1 = ab.dot(ap);
+	let d2 = ac.dot(ap);
+	if d1 <= 0_f32 && d2 <= 0_f32 {
+		return Vec3A::X;
+	}
+	let bp = p - b;
+	let d3 = ab.dot(bp);
+	let d4 = ac.dot(bp);
+	if d3 >= 0_f32 && d4 <= d3 {
+		return Vec3A::Y;
+	}
+	let vc = (d1 * d4) - (d3 * d2);
+	if vc <= 0_f32 && d1 >= 0_f32 && d3 <= 0_f32 {
+		let v = d1 / (d1 - d3);
+		return vec3a(1_f32 - v, v, 0_f32);
+	}
+	let cp = p - c;
+	let d5 = ab.dot(cp);
+	let d6 = ac.dot(cp);
+	if d6 >= 0_f32 && d5 <= d6 {
+		return Vec3A::Z;
+	}
+	let vb = (d5 * d2) - (d1 * d6);
+	if vb <= 0_f32 && d2 >= 0_f32 && d6 <= 0_f32 {
+		let w = d2 / (d2 - d6);
+		return vec3a(1_f32 - w, 0_f32, w);
+	}
+	let va = (d3 * d6) - (d5 * d4);
+	if va <= 0_f32 && (d4 - d3) >= 0_f32 && (d5 - d6) >= 0_f32 {
+		let w = (d4 - d3) / ((d4 - d3) + (d5 - d6));
+		return vec3a(0_f32, 1_f32 - w, w);
+	}
+	let denom = 1_f32 / (va + vb + vc);
+	let v = vb * denom;
+	let w = vc * denom;
+	vec3a(1_f32 - v - w, v, w)
+}
+
+pub struct MeshHit {
+	pub triangle: usize,
+	pub barycentric: Vec3A,
+	pub point: Vec3A,
+}
+
+/// A static triangle mesh. The sign comes from the closest triangle's winding,
+/// so it's only reliable for closed meshes and can be wrong right at edges.
+pub struct MeshField {
+	space: Arc<Spatial>,
+	common: FieldCommon,
+	/// Shared with any clones of this field, since meshes can be big.
+	pub(super) vertices: Arc<[Vec3A]>,
+	pub(super) triangles: Arc<[[usize; 3]]>,
+	/// Material id of each triangle, for feedback like which surface was touched.
+	pub(super) materials: Arc<[u32]>,
+}
+
+impl MeshField {
+	pub fn add_to(
+		node: &Arc<Node>,
+		vertices: Arc<[Vec3A]>,
+		triangles: Arc<[[usize; 3]]>,
+		materials: Arc<[u32]>,
+	) -> Result<()> {
+		ensure!(
+			node.spatial.get().is_some(),
+			"Internal: Node does not have a spatial attached!"
+		);
+		ensure!(
+			node.field.get().is_none(),
+			"Internal: Node already has a field attached!"
+		);
+		ensure!(!triangles.is_empty(), "Mesh field has no triangles");
+		ensure!(
+			triangles
+				.iter()
+				.flatten()
+				.all(|index| *index < vertices.len()),
+			"Mesh field has a triangle index out of range"
+		);
+		ensure!(
+			materials.len() == triangles.len(),
+			"Mesh field has {} material ids for {} triangles",
+			materials.len(),
+			triangles.len()
+		);
+		let mesh_field = MeshField {
+			space: node.spatial.get().unwrap().clone(),
+			common: Default::default(),
+			vertices,
+			triangles,
+			materials,
+		};
+		mesh_field.add_field_methods(node);
+		let _ = node.field.set(FIELD_REGISTRY.add(Field::Mesh(mesh_field)));
+		Ok(())
+	}
+
+	pub fn closest_triangle(&self, p: Vec3A) -> MeshHit {
+		self.triangles
+			.iter()
+			.enumerate()
+			.map(|(triangle, [a, b, c])| {
+				let (a, b, c) = (self.vertices[*a], self.vertices[*b], self.vertices[*c]);
+				let barycentric = closest_point_on_triangle(p, a, b, c);
+				MeshHit {
+					triangle,
+					barycentric,
+					point: (a * barycentric.x) + (b * barycentric.y) + (c * barycentric.z),
+				}
+			})
+			.min_by(|hit_a, hit_b| {
+				hit_a
+					.point
+					.distance_squared(p)
+					.total_cmp(&hit_b.point.distance_squared(p))
+			})
+			.unwrap()
+	}
+}
+
+impl FieldTrait for MeshField {
+	fn local_distance(&self, p: Vec3A) -> f32 {
+		let _stats = self.common.stats.record();
+		let hit = self.closest_triangle(p);
+		let [a, b, c] = self.triangles[hit.triangle];
+		let (a, b, c) = (self.vertices[a], self.vertices[b], self.vertices[c]);
+		let face_normal = (b - a).cross(c - a);
+		let distance = hit.point.distance(p);
+		if (p - hit.point).dot(face_normal) < 0_f32 {
+			-distance
+		} else {
+			distance
+		}
+	}
+	fn local_closest_point(&self, p: Vec3A, _r: f32) -> Vec3A {
+		self.closest_triangle(p).point
+	}
+	fn local_bounding_radius(&self) -> f32 {
+		self.vertices
+			.iter()
+			.map(|vertex| vertex.length())
+			.fold(0_f32, f32::max)
+	}
+	fn complexity(&self) -> u32 {
+		self.triangles.len() as u32
+	}
+	fn spatial_ref(&self) -> &Spatial {
+		self.space.as_ref()
+	}
+	fn common(&self) -> &FieldCommon {
+		&self.common
+	}
+}
+
+pub fn create_box_field_flex(_node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+	create_box_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_box_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "position", "rotation", "size"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(3))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into(),
+	);
+	let size = flex_to_size_vec3(flex_vec.idx(4), "Size")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	BoxField::add_to(&node, size.into())?;
+	Ok(())
+}
+
+/// The smallest axis aligned (in the parent's space) box containing all the points.
+pub fn create_box_field_from_points_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_box_field_from_points(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_box_field_from_points(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(&flex_vec, &["name", "parent", "points"])?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let points = flex_vec
+		.idx(2)
+		.get_vector()?
+		.iter()
+		.map(|point| flex_to_finite_vec3(point, "Point"))
+		.collect::<Result<Vec<Vec3A>>>()?;
+	ensure!(!points.is_empty(), "No points to fit a box field to");
+	let (min, max) = points.iter().fold(
+		(Vec3A::splat(f32::MAX), Vec3A::splat(f32::MIN)),
+		|(min, max), point| (min.min(*point), max.max(*point)),
+	);
+	let transform = Mat4::from_translation(((min + max) * 0.5_f32).into());
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	BoxField::add_to(&node, (max - min).into())?;
+	Ok(())
+}
+
+pub fn create_rounded_box_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_rounded_box_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_rounded_box_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "position", "rotation", "size", "rounding"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(3))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into(),
+	);
+	let size = flex_to_size_vec3(flex_vec.idx(4), "Size")?;
+	let rounding = flex_to_rounding(flex_vec.idx(5))?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	RoundedBoxField::add_to(&node, size.into(), rounding)?;
+	Ok(())
+}
+
+pub fn create_quad_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_quad_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_quad_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&[
+			"name", "parent", "position", "rotation", "width", "height", "radius",
+		],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(3))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into(),
+	);
+	let width = flex_to_size(flex_vec.idx(4), "Width")?;
+	let height = flex_to_size(flex_vec.idx(5), "Height")?;
+	let radius = flex_to_size(flex_vec.idx(6), "Radius")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	QuadField::add_to(&node, width, height, radius)?;
+	Ok(())
+}
+
+pub fn create_cylinder_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_cylinder_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_cylinder_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "position", "rotation", "length", "radius"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(3))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into(),
+	);
+	let length = flex_to_size(flex_vec.idx(4), "Length")?;
+	let radius = flex_to_size(flex_vec.idx(5), "Radius")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	CylinderField::add_to(&node, length, radius)?;
+	Ok(())
+}
+
+pub fn create_sphere_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_sphere_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_sphere_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(&flex_vec, &["name", "parent", "position", "radius"])?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform =
+		Mat4::from_translation(flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into());
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	SphereField::add_to(&node, flex_to_size(flex_vec.idx(3), "Radius")?)?;
+	Ok(())
+}
+
+/// A sphere containing all of `points` by Ritter's algorithm, so it's quick but can be
+/// up to about 5% bigger than the smallest one.
+fn bounding_sphere(points: &[Vec3A]) -> (Vec3A, f32) {
+	let farthest_from = |from: Vec3A| {
+		points
+			.iter()
+			.copied()
+			.max_by(|a, b| {
+				a.distance_squared(from)
+					.total_cmp(&b.distance_squared(from))
+			})
+			.unwrap()
+	};
+	let a = farthest_from(points[0]);
+	let b = farthest_from(a);
+	let mut center = (a + b) * 0.5_f32;
+	let mut radius = a.distance(b) * 0.5_f32;
+	for point in points {
+		let distance = point.distance(center);
+		if distance > radius {
+			// grow just enough to reach the point, keeping the far side where it was
+			let new_radius = (radius + distance) * 0.5_f32;
+			center += (*point - center) * ((new_radius - radius) / distance);
+			radius = new_radius;
+		}
+	}
+	(center, radius)
+}
+
+/// A sphere around all the points, or around the origins of all the children of a spatial if
+/// given its path instead, in the parent's space.
+pub fn create_sphere_field_bounding_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_sphere_field_bounding(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_sphere_field_bounding(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(&flex_vec, &["name", "parent", "points"])?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let points = if flex_vec.idx(2).flexbuffer_type() == flexbuffers::FlexBufferType::String {
+		let spatial = get_reference_space_flex(calling_client, flex_vec.idx(2).as_str())?;
+		calling_client
+			.scenegraph
+			.get_nodes()
+			.into_iter()
+			.filter_map(|node| node.spatial.get().cloned())
+			.filter(|child| {
+				child
+					.ancestor(1)
+					.map_or(false, |child_parent| Arc::ptr_eq(&child_parent, &spatial))
+			})
+			.map(|child| {
+				Spatial::space_to_space_matrix(Some(child.as_ref()), Some(parent.as_ref()))
+					.transform_point3a(Vec3A::ZERO)
+			})
+			.collect()
+	} else {
+		flex_vec
+			.idx(2)
+			.get_vector()?
+			.iter()
+			.map(|point| flex_to_finite_vec3(point, "Point"))
+			.collect::<Result<Vec<Vec3A>>>()?
+	};
+	ensure!(!points.is_empty(), "No points to fit a sphere field to");
+	let (center, radius) = bounding_sphere(&points);
+	let transform = Mat4::from_translation(center.into());
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	SphereField::add_to(&node, radius)?;
+	Ok(())
+}
+
+pub fn create_cone_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_cone_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_cone_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "position", "rotation", "angle", "length"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(3))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into(),
+	);
+	let angle = flex_vec.idx(4).as_f32();
+	let length = flex_to_size(flex_vec.idx(5), "Length")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	ConeField::add_to(&node, angle, length)?;
+	Ok(())
+}
+
+/// A cone from `apex` towards `direction`, for "what's in front of this" queries.
+pub fn create_spotlight_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_spotlight_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_spotlight_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "apex", "direction", "half angle", "range"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let apex = flex_to_finite_vec3(flex_vec.idx(2), "Apex")?;
+	let direction = flex_to_finite_vec3(flex_vec.idx(3), "Direction")?;
+	ensure!(direction.length_squared() > 0_f32, "Direction is zero");
+	let transform = Mat4::from_rotation_translation(
+		Quat::from_rotation_arc(Vec3::Z, Vec3::from(direction).normalize()),
+		apex.into(),
+	);
+	let half_angle = flex_vec.idx(4).as_f32();
+	let range = flex_to_size(flex_vec.idx(5), "Range")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	ConeField::add_to(&node, half_angle, range)?;
+	Ok(())
+}
+
+pub fn create_capped_torus_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_capped_torus_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_capped_torus_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&[
+			"name",
+			"parent",
+			"position",
+			"rotation",
+			"major radius",
+			"minor radius",
+			"angle",
+		],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(3))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into(),
+	);
+	let major_radius = flex_to_size(flex_vec.idx(4), "Major radius")?;
+	let minor_radius = flex_to_size(flex_vec.idx(5), "Minor radius")?;
+	let angle = flex_vec.idx(6).as_f32();
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	CappedTorusField::add_to(&node, major_radius, minor_radius, angle)?;
+	Ok(())
+}
+
+pub fn create_tapered_capsule_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_tapered_capsule_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_tapered_capsule_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&[
+			"name",
+			"parent",
+			"position",
+			"rotation",
+			"bottom radius",
+			"top radius",
+			"length",
+		],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(3))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into(),
+	);
+	let bottom_radius = flex_to_size(flex_vec.idx(4), "Bottom radius")?;
+	let top_radius = flex_to_size(flex_vec.idx(5), "Top radius")?;
+	let length = flex_to_size(flex_vec.idx(6), "Length")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	TaperedCapsuleField::add_to(&node, bottom_radius, top_radius, length)?;
+	Ok(())
+}
+
+pub fn create_pyramid_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_pyramid_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_pyramid_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&[
+			"name",
+			"parent",
+			"position",
+			"rotation",
+			"half width",
+			"height",
+		],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(3))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into(),
+	);
+	let half_width = flex_to_size(flex_vec.idx(4), "Half width")?;
+	let height = flex_to_size(flex_vec.idx(5), "Height")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	PyramidField::add_to(&node, half_width, height)?;
+	Ok(())
+}
+
+pub fn create_polyline_capsule_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_polyline_capsule_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_polyline_capsule_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "position", "rotation", "points", "radius"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = get_transform_pose_flex(&flex_vec.idx(2), &flex_vec.idx(3))?;
+	let points = flex_vec
+		.idx(4)
+		.get_vector()?
+		.iter()
+		.map(|point| flex_to_finite_vec3(point, "Point"))
+		.collect::<Result<Vec<Vec3A>>>()?;
+	ensure!(!points.is_empty(), "Polyline capsule field has no points");
+	let radius = flex_to_size(flex_vec.idx(5), "Radius")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	PolylineCapsuleField::add_to(&node, points, radius)?;
+	Ok(())
+}
+
+pub fn create_wedge_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_wedge_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_wedge_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&[
+			"name",
+			"parent",
+			"position",
+			"rotation",
+			"start angle",
+			"end angle",
+			"radius",
+			"height",
+		],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(3))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into(),
+	);
+	let start_angle = flex_vec.idx(4).as_f32();
+	let end_angle = flex_vec.idx(5).as_f32();
+	let radius = flex_to_size(flex_vec.idx(6), "Radius")?;
+	let height = flex_to_size(flex_vec.idx(7), "Height")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	WedgeField::add_to(&node, start_angle, end_angle, radius, height)?;
+	Ok(())
+}
+
+pub fn create_octahedron_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_octahedron_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_octahedron_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "position", "rotation", "size"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(3))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into(),
+	);
+	let size = flex_to_size(flex_vec.idx(4), "Size")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	OctahedronField::add_to(&node, size)?;
+	Ok(())
+}
+
+pub fn create_slab_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_slab_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_slab_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&["name", "parent", "position", "rotation", "thickness"],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = Mat4::from_rotation_translation(
+		flex_to_quat!(flex_vec.idx(3))
+			.ok_or_else(|| anyhow!("Rotation not found"))?
+			.into(),
+		flex_to_finite_vec3(flex_vec.idx(2), "Position")?.into(),
+	);
+	let thickness = flex_to_size(flex_vec.idx(4), "Thickness")?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	SlabField::add_to(&node, thickness)?;
+	Ok(())
+}
+
+pub fn create_mesh_field_flex(
+	_node: &Node,
+	calling_client: Arc<Client>,
+	data: &[u8],
+) -> Result<()> {
+	create_mesh_field(
+		&calling_client,
+		flexbuffers::Reader::get_root(data)?.get_vector()?,
+	)
+}
+
+pub(super) fn create_mesh_field(
+	calling_client: &Arc<Client>,
+	flex_vec: flexbuffers::VectorReader<&[u8]>,
+) -> Result<()> {
+	check_flex_args(
+		&flex_vec,
+		&[
+			"name", "parent", "position", "rotation", "vertices", "indices",
+		],
+	)?;
+	let node = Node::create(
+		calling_client,
+		FIELD_PARENT_PATH,
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = get_transform_pose_flex(&flex_vec.idx(2), &flex_vec.idx(3))?;
+	let coordinates: Vec<f32> = flex_vec
+		.idx(4)
+		.get_vector()?
+		.iter()
+		.map(|coordinate| coordinate.as_f32())
+		.collect();
+	ensure!(
+		coordinates.len() % 3 == 0,
+		"Mesh vertex coordinates aren't a multiple of 3"
+	);
+	let vertices: Vec<Vec3A> = coordinates
+		.chunks_exact(3)
+		.map(|vertex| vec3a(vertex[0], vertex[1], vertex[2]))
+		.collect();
+	ensure!(
+		vertices.iter().all(|vertex| vertex.is_finite()),
+		"Mesh has a non-finite vertex"
+	);
+	let indices: Vec<usize> = flex_vec
+		.idx(5)
+		.get_vector()?
+		.iter()
+		.map(|index| index.as_u32() as usize)
+		.collect();
+	ensure!(
+		indices.len() % 3 == 0,
+		"Mesh triangle indices aren't a multiple of 3"
+	);
+	let triangles: Vec<[usize; 3]> = indices
+		.chunks_exact(3)
+		.map(|triangle| [triangle[0], triangle[1], triangle[2]])
+		.collect();
+	ensure!(!triangles.is_empty(), "Mesh field has no triangles");
+	ensure!(
+		indices.iter().all(|index| *index < vertices.len()),
+		"Mesh field has a triangle index out of range"
+	);
+	let materials: Vec<u32> = if flex_vec.idx(6).flexbuffer_type().is_null() {
+		vec![0; triangles.len()]
+	} else {
+		flex_vec
+			.idx(6)
+			.get_vector()?
+			.iter()
+			.map(|material| material.as_u32())
+			.collect()
+	};
+	ensure!(
+		materials.len() == triangles.len(),
+		"Mesh field has {} material ids for {} triangles",
+		materials.len(),
+		triangles.len()
+	);
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	MeshField::add_to(&node, vertices.into(), triangles.into(), materials.into())?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::nodes::field::tests::*;
+	use crate::nodes::field::{next_random, random_direction, ray_march, Ray};
+	use libstardustxr::push_to_vec;
+	use std::sync::atomic::AtomicBool;
+
+	#[test]
+	fn pyramid_distances() {
+		let client = test_client();
+		let pyramid = create_field(
+			&client,
+			"createPyramidField",
+			"pyramid",
+			Vec3::ZERO,
+			|vec| {
+				vec.push(0.5_f32);
+				vec.push(1_f32);
+			},
+		);
+		// on the surface at the apex, the base centre and a base edge
+		assert_near(distance_at(&client, &pyramid, Vec3::Y), 0_f32, 0.0001_f32);
+		assert_near(
+			distance_at(&client, &pyramid, Vec3::ZERO),
+			0_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &pyramid, vec3(0.5_f32, 0_f32, 0.2_f32)),
+			0_f32,
+			0.0001_f32,
+		);
+		// off a face along its normal, above the apex and below the base
+		let face_normal = vec3(1_f32, 0.5_f32, 0_f32).normalize();
+		let off_face = vec3(0.25_f32, 0.5_f32, 0_f32) + (face_normal * 0.1_f32);
+		assert_near(
+			distance_at(&client, &pyramid, off_face),
+			0.1_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &pyramid, Vec3::Y * 1.5_f32),
+			0.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &pyramid, Vec3::Y * -0.3_f32),
+			0.3_f32,
+			0.0001_f32,
+		);
+		assert!(distance_at(&client, &pyramid, Vec3::Y * 0.25_f32) < 0_f32);
+		assert_near(
+			distance_at(&client, &pyramid, Vec3::Y * 0.01_f32),
+			-0.01_f32,
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	fn cylinder_reads_its_size_arguments() {
+		let client = test_client();
+		let cylinder = create_field(&client, "createCylinderField", "cylinder", Vec3::X, |vec| {
+			vec.push(2_f32);
+			vec.push(0.5_f32);
+		});
+		assert_eq!(cylinder_size(&cylinder), (2_f32, 0.5_f32));
+		let missing_radius = flexbuffer_from_vector_arguments(|vec| {
+			vec.push("short");
+			vec.push("/");
+			push_to_vec!(
+				vec,
+				mint::Vector3::from(Vec3::ZERO),
+				mint::Quaternion::from(Quat::IDENTITY)
+			);
+			vec.push(2_f32);
+		});
+		assert!(client
+			.scenegraph
+			.get_node("/field")
+			.unwrap()
+			.send_local_signal(client.clone(), "createCylinderField", &missing_radius)
+			.is_err());
+		assert!(client.scenegraph.get_node("/field/short").is_none());
+	}
+
+	#[test]
+	fn cylinder_self_test_passes() {
+		let result = method(&test_client(), "/field", "cylinderSelfTest", &[]).unwrap();
+		let result = flexbuffers::Reader::get_root(result.as_slice())
+			.unwrap()
+			.as_vector();
+		assert!(result.idx(0).as_bool());
+		assert_near(result.idx(1).as_f32(), result.idx(2).as_f32(), 0.0001_f32);
+		assert_near(result.idx(3).as_f32(), result.idx(4).as_f32(), 0.0001_f32);
+
+		// and it catches the length being used as the radius
+		fn swapped(p: Vec3A, length: f32, _radius: f32) -> f32 {
+			cylinder_distance(p, length, length)
+		}
+		assert!(!check_cylinder_distance(swapped).0);
+	}
+
+	#[test]
+	fn cylinder_distances() {
+		let client = test_client();
+		let cylinder = create_field(
+			&client,
+			"createCylinderField",
+			"cylinder",
+			Vec3::ZERO,
+			|vec| {
+				vec.push(2_f32);
+				vec.push(0.5_f32);
+			},
+		);
+		assert_near(
+			distance_at(&client, &cylinder, Vec3::Z * 1.5_f32),
+			0.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &cylinder, vec3(1_f32, 0_f32, 0.5_f32)),
+			0.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &cylinder, vec3(0.8_f32, 0_f32, 1.4_f32)),
+			0.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &cylinder, Vec3::ZERO),
+			-0.5_f32,
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	fn cylinder_resize_is_never_torn() {
+		let client = test_client();
+		let cylinder = create_field(
+			&client,
+			"createCylinderField",
+			"cylinder",
+			Vec3::ZERO,
+			|vec| {
+				vec.push(2_f32);
+				vec.push(0.5_f32);
+			},
+		);
+		let field = cylinder.field.get().unwrap().clone();
+		// a point whose distance tells both sizes apart from a mix of the two
+		let p = vec3a(0.75_f32, 0_f32, 1.25_f32);
+		let distance_at_size = |length: f32, radius: f32| {
+			if let Field::Cylinder(cylinder_field) = field.as_ref() {
+				cylinder_field.set_size(length, radius);
+			}
+			field.local_distance(p)
+		};
+		let expected = [
+			distance_at_size(4_f32, 1_f32),
+			distance_at_size(2_f32, 0.5_f32),
+		];
+		assert!((expected[0] - expected[1]).abs() > 0.1_f32);
+		let done = Arc::new(AtomicBool::new(false));
+
+		let resizer = {
+			let field = field.clone();
+			let done = done.clone();
+			std::thread::spawn(move || {
+				if let Field::Cylinder(cylinder_field) = field.as_ref() {
+					for i in 0..100_000 {
+						if i % 2 == 0 {
+							cylinder_field.set_size(4_f32, 1_f32);
+						} else {
+							cylinder_field.set_size(2_f32, 0.5_f32);
+						}
+					}
+				}
+				done.store(true, Ordering::Relaxed);
+			})
+		};
+		let mut queries = 0;
+		while !done.load(Ordering::Relaxed) || queries == 0 {
+			let distance = field.local_distance(p);
+			assert!(
+				expected.iter().any(|e| (distance - e).abs() < 0.0001_f32),
+				"torn size gave a distance of {}",
+				distance
+			);
+			queries += 1;
+		}
+		resizer.join().unwrap();
+	}
+
+	#[test]
+	fn negative_or_non_finite_sizes_are_rejected() {
+		let client = test_client();
+		let create =
+			|create_signal: &str, name: &str, args: &dyn Fn(&mut flexbuffers::VectorBuilder)| {
+				client
+					.scenegraph
+					.get_node("/field")
+					.unwrap()
+					.send_local_signal(
+						client.clone(),
+						create_signal,
+						&flexbuffer_from_vector_arguments(|vec| {
+							vec.push(name);
+							vec.push("/");
+							push_to_vec!(
+								vec,
+								mint::Vector3::from(Vec3::ZERO),
+								mint::Quaternion::from(Quat::IDENTITY)
+							);
+							args(vec);
+						}),
+					)
+			};
+		assert!(create("createBoxField", "inside_out", &|vec| {
+			push_to_vec!(vec, mint::Vector3::from(vec3(1_f32, -1_f32, 1_f32)));
+		})
+		.is_err());
+		assert!(create("createCylinderField", "nan", &|vec| {
+			vec.push(1_f32);
+			vec.push(f32::NAN);
+		})
+		.is_err());
+		assert!(create("createCylinderField", "infinite", &|vec| {
+			vec.push(f32::INFINITY);
+			vec.push(1_f32);
+		})
+		.is_err());
+		for name in ["inside_out", "nan", "infinite"] {
+			assert!(client
+				.scenegraph
+				.get_node(&format!("/field/{}", name))
+				.is_none());
+		}
+
+		let sphere = create_sphere(&client, "resized", Vec3::ZERO, 1_f32);
+		let set_radius = |radius: f32| {
+			sphere.send_local_signal(client.clone(), "setRadius", &flexbuffers::singleton(radius))
+		};
+		assert!(set_radius(-1_f32).is_err());
+		assert_near(
+			distance_at(&client, &sphere, Vec3::X * 2_f32),
+			1_f32,
+			0.0001_f32,
+		);
+		assert!(set_radius(0_f32).is_ok());
+	}
+
+	#[test]
+	fn spotlight_contains_points_within_its_angle_and_range() {
+		let client = test_client();
+		signal(
+			&client,
+			"/field",
+			"createSpotlightField",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("spotlight");
+				vec.push("/");
+				push_to_vec!(
+					vec,
+					mint::Vector3::from(Vec3::Y),
+					mint::Vector3::from(-Vec3::X),
+					0.5_f32,
+					5_f32
+				);
+			}),
+		);
+		let spotlight = client.scenegraph.get_node("/field/spotlight").unwrap();
+		let at_angle = |angle: f32, range: f32| {
+			let (sin, cos) = angle.sin_cos();
+			Vec3::Y + (vec3(-cos, sin, 0_f32) * range)
+		};
+		assert!(distance_at(&client, &spotlight, at_angle(0_f32, 2_f32)) < 0_f32);
+		assert!(distance_at(&client, &spotlight, at_angle(0.45_f32, 2_f32)) < 0_f32);
+		assert!(distance_at(&client, &spotlight, at_angle(0.55_f32, 2_f32)) > 0_f32);
+		assert!(distance_at(&client, &spotlight, at_angle(-0.55_f32, 2_f32)) > 0_f32);
+		assert!(distance_at(&client, &spotlight, at_angle(0_f32, 5.1_f32)) > 0_f32);
+	}
+
+	#[test]
+	fn capped_torus_band_versus_its_open_ends() {
+		let client = test_client();
+		let torus = create_field(
+			&client,
+			"createCappedTorusField",
+			"dial",
+			Vec3::ZERO,
+			|vec| {
+				vec.push(1_f32);
+				vec.push(0.1_f32);
+				vec.push(1_f32);
+			},
+		);
+		let on_circle = |angle: f32| vec3(angle.sin(), angle.cos(), 0_f32);
+		for angle in [0_f32, 0.5_f32, -0.9_f32] {
+			assert_near(
+				distance_at(&client, &torus, on_circle(angle)),
+				-0.1_f32,
+				0.0001_f32,
+			);
+		}
+		// past the ends it's the distance to the end cap, not to the rest of the circle
+		let end_distance = 2_f32 * 0.5_f32.sin() - 0.1_f32;
+		assert_near(
+			distance_at(&client, &torus, on_circle(2_f32)),
+			end_distance,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &torus, on_circle(-2_f32)),
+			end_distance,
+			0.0001_f32,
+		);
+		assert!(distance_at(&client, &torus, on_circle(std::f32::consts::PI)) > 1_f32);
+	}
+
+	#[test]
+	fn box_distance_metrics() {
+		let client = test_client();
+		let box_node = create_box(&client, "metric", Vec3::ZERO, Vec3::ONE);
+		let point = vec3(1.5_f32, -1_f32, 0.8_f32);
+		let gaps = vec3(1_f32, 0.5_f32, 0.3_f32);
+		let distance = |metric: &str| {
+			read_f32(
+				&method(
+					&client,
+					box_node.get_path(),
+					"distance",
+					&query_args(point, |options| options.push("metric", metric)),
+				)
+				.unwrap(),
+			)
+		};
+		assert_near(distance("chebyshev"), gaps.max_element(), 0.0001_f32);
+		assert_near(distance("manhattan"), gaps.x + gaps.y + gaps.z, 0.0001_f32);
+		assert_near(distance("euclidean"), gaps.length(), 0.0001_f32);
+		assert_near(
+			distance_at(&client, &box_node, point),
+			gaps.length(),
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	fn tapered_capsule_body_and_caps() {
+		let client = test_client();
+		let capsule = create_field(
+			&client,
+			"createTaperedCapsuleField",
+			"finger",
+			Vec3::ZERO,
+			|vec| {
+				vec.push(0.5_f32);
+				vec.push(0.2_f32);
+				vec.push(1_f32);
+			},
+		);
+		// past each cap it's the distance to that end's sphere
+		assert_near(
+			distance_at(&client, &capsule, -Vec3::Y),
+			0.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &capsule, Vec3::Y * 1.5_f32),
+			0.3_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &capsule, vec3(0_f32, 1.3_f32, 0.3_f32)),
+			Vec2::splat(0.3_f32).length() - 0.2_f32,
+			0.0001_f32,
+		);
+		// beside the body it's the distance to the line touching both spheres
+		let b = 0.3_f32;
+		let a = (1_f32 - (b * b)).sqrt();
+		let beside = vec3(1_f32, 0.5_f32, 0_f32);
+		assert_near(
+			distance_at(&client, &capsule, beside),
+			a + (b * 0.5_f32) - 0.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &capsule, vec3(0_f32, 0.5_f32, -1_f32)),
+			distance_at(&client, &capsule, beside),
+			0.0001_f32,
+		);
+		assert!(distance_at(&client, &capsule, Vec3::Y * 0.5_f32) < 0_f32);
+	}
+
+	#[test]
+	fn closest_triangle_of_a_two_triangle_quad() {
+		let client = test_client();
+		let mesh = create_field(&client, "createMeshField", "quad_mesh", Vec3::ZERO, |vec| {
+			let mut vertices = vec.start_vector();
+			for vertex in [Vec3::ZERO, Vec3::X, vec3(1_f32, 1_f32, 0_f32), Vec3::Y] {
+				for coordinate in vertex.to_array() {
+					vertices.push(coordinate);
+				}
+			}
+			vertices.end_vector();
+			let mut indices = vec.start_vector();
+			for index in [0_u32, 1, 2, 0, 2, 3] {
+				indices.push(index);
+			}
+			indices.end_vector();
+		});
+		let closest_triangle = |point: Vec3| {
+			let hit = method(
+				&client,
+				mesh.get_path(),
+				"closest_triangle",
+				&point_args(point),
+			)
+			.unwrap();
+			let hit = flexbuffers::Reader::get_root(hit.as_slice())
+				.unwrap()
+				.as_vector();
+			let barycentric: Vec3 = flex_to_vec3!(hit.idx(1)).unwrap().into();
+			(hit.idx(0).as_u32(), barycentric)
+		};
+		let (triangle, barycentric) = closest_triangle(vec3(0.75_f32, 0.25_f32, 0.5_f32));
+		assert_eq!(triangle, 0);
+		assert!(
+			barycentric.abs_diff_eq(vec3(0.25_f32, 0.5_f32, 0.25_f32), 0.0001_f32),
+			"{}",
+			barycentric
+		);
+		let (triangle, barycentric) = closest_triangle(vec3(0.2_f32, 0.6_f32, -0.3_f32));
+		assert_eq!(triangle, 1);
+		assert!(
+			barycentric.abs_diff_eq(vec3(0.4_f32, 0.2_f32, 0.4_f32), 0.0001_f32),
+			"{}",
+			barycentric
+		);
+
+		let sphere = create_sphere(&client, "not_a_mesh", Vec3::ZERO, 1_f32);
+		assert!(method(
+			&client,
+			sphere.get_path(),
+			"closest_triangle",
+			&point_args(Vec3::ZERO)
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn three_point_polyline_capsule() {
+		let client = test_client();
+		let limb = create_field(
+			&client,
+			"createPolylineCapsuleField",
+			"limb",
+			Vec3::ZERO,
+			|vec| {
+				let mut points = vec.start_vector();
+				for point in [Vec3::ZERO, Vec3::X, vec3(1_f32, 1_f32, 0_f32)] {
+					push_to_vec!(&mut points, mint::Vector3::from(point));
+				}
+				points.end_vector();
+				vec.push(0.1_f32);
+			},
+		);
+		// along each segment
+		assert_near(
+			distance_at(&client, &limb, vec3(0.5_f32, 0.3_f32, 0_f32)),
+			0.2_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &limb, vec3(1.4_f32, 0.5_f32, 0_f32)),
+			0.3_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &limb, vec3(1_f32, 0.5_f32, 0_f32)),
+			-0.1_f32,
+			0.0001_f32,
+		);
+		// around the middle joint, on the outside and the inside of the bend
+		assert_near(
+			distance_at(&client, &limb, vec3(1.3_f32, -0.4_f32, 0_f32)),
+			0.4_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &limb, vec3(0.8_f32, 0.2_f32, 0_f32)),
+			0.1_f32,
+			0.0001_f32,
+		);
+		// past the free ends
+		assert_near(
+			distance_at(&client, &limb, vec3(-0.5_f32, 0_f32, 0_f32)),
+			0.4_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &limb, vec3(1_f32, 1_f32, 0.6_f32)),
+			0.5_f32,
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	fn wedge_inside_and_outside_its_angles() {
+		let client = test_client();
+		let wedge = create_field(&client, "createWedgeField", "slice", Vec3::ZERO, |vec| {
+			vec.push(0_f32);
+			vec.push(std::f32::consts::FRAC_PI_2);
+			vec.push(1_f32);
+			vec.push(1_f32);
+		});
+		let at_angle = |angle: f32, radius: f32| vec3(angle.cos(), 0_f32, angle.sin()) * radius;
+		let quarter = std::f32::consts::FRAC_PI_4;
+		// halfway between the edges, closer to them than to the arc or the caps
+		let edge_distance = 0.5_f32 * quarter.sin();
+		assert_near(
+			distance_at(&client, &wedge, at_angle(quarter, 0.5_f32)),
+			-edge_distance,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &wedge, at_angle(quarter, 2_f32)),
+			1_f32,
+			0.0001_f32,
+		);
+		// outside the angular range, beside the start edge and behind the tip
+		assert_near(
+			distance_at(&client, &wedge, at_angle(-quarter, 0.5_f32)),
+			edge_distance,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &wedge, at_angle(-3_f32 * quarter, 0.5_f32)),
+			0.5_f32,
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	fn octahedron_at_a_vertex_a_face_and_an_edge() {
+		let client = test_client();
+		let octahedron = create_field(
+			&client,
+			"createOctahedronField",
+			"diamond",
+			Vec3::ZERO,
+			|vec| {
+				vec.push(1_f32);
+			},
+		);
+		let inv_sqrt_3 = 1_f32 / 3_f32.sqrt();
+		assert_near(
+			distance_at(&client, &octahedron, Vec3::X * 2_f32),
+			1_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &octahedron, -Vec3::Y * 3_f32),
+			2_f32,
+			0.0001_f32,
+		);
+		// straight out from the face x + y + z = 1
+		assert_near(
+			distance_at(&client, &octahedron, Vec3::ONE),
+			2_f32 * inv_sqrt_3,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &octahedron, Vec3::ZERO),
+			-inv_sqrt_3,
+			0.0001_f32,
+		);
+		// out from the middle of the edge between the +X and +Y vertices
+		assert_near(
+			distance_at(&client, &octahedron, vec3(1_f32, 1_f32, 0_f32)),
+			0.5_f32.sqrt(),
+			0.0001_f32,
+		);
+
+		signal(
+			&client,
+			octahedron.get_path(),
+			"setSize",
+			&flexbuffers::singleton(2_f32),
+		);
+		assert_near(
+			distance_at(&client, &octahedron, Vec3::X * 2_f32),
+			0_f32,
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	fn zero_size_fields_stay_finite() {
+		let client = test_client();
+		let fields = [
+			create_sphere(&client, "empty_sphere", Vec3::ZERO, 0_f32),
+			create_box(&client, "empty_box", Vec3::ZERO, Vec3::ZERO),
+			create_field(
+				&client,
+				"createRoundedBoxField",
+				"empty_rounded_box",
+				Vec3::ZERO,
+				|vec| {
+					push_to_vec!(vec, mint::Vector3::from(Vec3::ZERO));
+					vec.push(0_f32);
+				},
+			),
+			create_field(
+				&client,
+				"createConeField",
+				"empty_cone",
+				Vec3::ZERO,
+				|vec| {
+					vec.push(0_f32);
+					vec.push(0_f32);
+				},
+			),
+			create_field(
+				&client,
+				"createPyramidField",
+				"empty_pyramid",
+				Vec3::ZERO,
+				|vec| {
+					vec.push(0_f32);
+					vec.push(0_f32);
+				},
+			),
+			create_field(
+				&client,
+				"createTaperedCapsuleField",
+				"empty_capsule",
+				Vec3::ZERO,
+				|vec| {
+					vec.push(0_f32);
+					vec.push(0_f32);
+					vec.push(0_f32);
+				},
+			),
+		];
+		let point = vec3(1_f32, 0.5_f32, 0.2_f32);
+		for field in &fields {
+			let distance = distance_at(&client, field, point);
+			assert!(
+				distance.is_finite(),
+				"{} distance {}",
+				field.get_path(),
+				distance
+			);
+			let proximity = read_f32(
+				&method(&client, field.get_path(), "proximity", &point_args(point)).unwrap(),
+			);
+			assert!(
+				(0_f32..=1_f32).contains(&proximity),
+				"{} proximity {}",
+				field.get_path(),
+				proximity
+			);
+
+			let result = ray_march(
+				Ray {
+					origin: vec3(-3_f32, 0.5_f32, 0.2_f32),
+					direction: Vec3::X,
+					space: get_reference_space_flex(&client, "/").unwrap(),
+					max_steps: None,
+				},
+				field.field.get().unwrap(),
+			);
+			assert!(result.ray_steps > 0);
+			assert!(result.distance.is_finite() && result.ray_length.is_finite());
+		}
+	}
+
+	#[test]
+	fn per_axis_rounding_makes_corners_asymmetric() {
+		let client = test_client();
+		let rounded_box = create_field(
+			&client,
+			"createRoundedBoxField",
+			"panel",
+			Vec3::ZERO,
+			|vec| {
+				push_to_vec!(vec, mint::Vector3::from(Vec3::splat(2_f32)));
+				vec.push(0.2_f32);
+			},
+		);
+		let beside_x = vec3(1.2_f32, 1_f32, 0_f32);
+		let beside_y = vec3(1_f32, 1.2_f32, 0_f32);
+		// a scalar rounds every axis the same, so the edge is symmetric
+		assert_near(
+			distance_at(&client, &rounded_box, beside_x),
+			distance_at(&client, &rounded_box, beside_y),
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &rounded_box, Vec3::X * 2_f32),
+			1_f32,
+			0.0001_f32,
+		);
+
+		signal(
+			&client,
+			rounded_box.get_path(),
+			"setRounding",
+			&flexbuffers::singleton(&[0.5_f32, 0.1_f32, 0.1_f32][..]),
+		);
+		let (x_distance, y_distance) = (
+			distance_at(&client, &rounded_box, beside_x),
+			distance_at(&client, &rounded_box, beside_y),
+		);
+		assert!(
+			(x_distance - y_distance).abs() > 0.05_f32,
+			"{} vs {}",
+			x_distance,
+			y_distance
+		);
+		// the faces themselves don't move
+		assert_near(
+			distance_at(&client, &rounded_box, Vec3::X * 2_f32),
+			1_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &rounded_box, Vec3::Y * 2_f32),
+			1_f32,
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	fn box_from_points_is_the_smallest_box_around_them() {
+		let client = test_client();
+		let points = [
+			vec3(0.5_f32, 1_f32, -2_f32),
+			vec3(-1_f32, 0.25_f32, 0_f32),
+			vec3(2_f32, -0.5_f32, 1_f32),
+			vec3(0_f32, 3_f32, 0.5_f32),
+			vec3(0.3_f32, 0.4_f32, 0.2_f32),
+		];
+		signal(
+			&client,
+			"/field",
+			"createBoxFieldFromPoints",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("fitted");
+				vec.push("/");
+				let mut point_vec = vec.start_vector();
+				for point in points {
+					push_to_vec!(&mut point_vec, mint::Vector3::from(point));
+				}
+				point_vec.end_vector();
+			}),
+		);
+		let fitted = client.scenegraph.get_node("/field/fitted").unwrap();
+		for point in points {
+			assert!(
+				distance_at(&client, &fitted, point) <= 0.0001_f32,
+				"{} is outside",
+				point
+			);
+		}
+		// every face touches a point, so nothing smaller would fit them
+		let (min, max) = (vec3(-1_f32, -0.5_f32, -2_f32), vec3(2_f32, 3_f32, 1_f32));
+		let center = (min + max) * 0.5_f32;
+		for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+			let to_min = center + (min - center) * axis;
+			let to_max = center + (max - center) * axis;
+			assert_near(distance_at(&client, &fitted, to_min), 0_f32, 0.0001_f32);
+			assert_near(distance_at(&client, &fitted, to_max), 0_f32, 0.0001_f32);
+		}
+	}
+
+	#[test]
+	fn box_closest_features() {
+		let client = test_client();
+		let box_node = create_box(&client, "featured", Vec3::ZERO, Vec3::splat(2_f32));
+		let sphere = create_sphere(&client, "featureless", Vec3::X * 5_f32, 1_f32);
+		let closest_feature = |node: &Node, point: Vec3| {
+			let result = method(
+				&client,
+				node.get_path(),
+				"closest_feature",
+				&point_args(point),
+			)
+			.unwrap();
+			let result = flexbuffers::Reader::get_root(result.as_slice())
+				.unwrap()
+				.as_vector();
+			let closest_point: Vec3 = flex_to_vec3!(result.idx(1)).unwrap().into();
+			(result.idx(0).as_str().to_string(), closest_point)
+		};
+		let (feature, point) = closest_feature(&box_node, vec3(1.5_f32, 1.2_f32, 1.3_f32));
+		assert_eq!(feature, "vertex");
+		assert!(point.abs_diff_eq(Vec3::ONE, 0.001_f32), "{}", point);
+		let (feature, point) = closest_feature(&box_node, vec3(0_f32, 2_f32, 0_f32));
+		assert_eq!(feature, "face");
+		assert!(point.abs_diff_eq(Vec3::Y, 0.001_f32), "{}", point);
+		let (feature, point) = closest_feature(&box_node, vec3(1.5_f32, 0.2_f32, 1.5_f32));
+		assert_eq!(feature, "edge");
+		assert!(
+			point.abs_diff_eq(vec3(1_f32, 0.2_f32, 1_f32), 0.001_f32),
+			"{}",
+			point
+		);
+		// fields without distinct features just report their surface
+		assert_eq!(closest_feature(&sphere, Vec3::ZERO).0, "surface");
+	}
+
+	#[test]
+	fn quad_distances() {
+		let client = test_client();
+		let quad = create_field(
+			&client,
+			"createQuadField",
+			"panel_surface",
+			Vec3::ZERO,
+			|vec| {
+				vec.push(2_f32);
+				vec.push(1_f32);
+				vec.push(0.1_f32);
+			},
+		);
+		// above the centre and along the thin axis through it
+		assert_near(
+			distance_at(&client, &quad, Vec3::Z * 0.5_f32),
+			0.4_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &quad, -Vec3::Z * 0.5_f32),
+			0.4_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &quad, Vec3::Z * 0.1_f32),
+			0_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &quad, Vec3::ZERO),
+			-0.1_f32,
+			0.0001_f32,
+		);
+		// past an edge and a corner, where the edge radius rounds it off
+		assert_near(
+			distance_at(&client, &quad, Vec3::X * 1.5_f32),
+			0.4_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &quad, Vec3::Y * 1.5_f32),
+			0.9_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &quad, vec3(1.3_f32, 0.9_f32, 0_f32)),
+			0.4_f32,
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	fn slab_distances_and_normals() {
+		let client = test_client();
+		let slab = create_field(&client, "createSlabField", "band", Vec3::ZERO, |vec| {
+			vec.push(1_f32);
+		});
+		let normal_at = |point: Vec3| {
+			let normal = method(&client, slab.get_path(), "normal", &point_args(point)).unwrap();
+			let normal: Vec3 =
+				flex_to_vec3!(flexbuffers::Reader::get_root(normal.as_slice()).unwrap())
+					.unwrap()
+					.into();
+			normal
+		};
+		assert_near(
+			distance_at(&client, &slab, vec3(100_f32, 0.2_f32, -50_f32)),
+			-0.3_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &slab, Vec3::Y * 2_f32),
+			1.5_f32,
+			0.0001_f32,
+		);
+		assert_near(
+			distance_at(&client, &slab, -Vec3::Y * 2_f32),
+			1.5_f32,
+			0.0001_f32,
+		);
+		// the normal only depends on which side of the middle the point is
+		for x in [-10_f32, 0_f32, 3_f32] {
+			assert_eq!(normal_at(vec3(x, 2_f32, x)), Vec3::Y);
+			assert_eq!(normal_at(vec3(x, -0.1_f32, -x)), -Vec3::Y);
+		}
+
+		signal(
+			&client,
+			slab.get_path(),
+			"setThickness",
+			&flexbuffers::singleton(4_f32),
+		);
+		assert_near(
+			distance_at(&client, &slab, Vec3::Y * 2_f32),
+			0_f32,
+			0.0001_f32,
+		);
+	}
+
+	#[test]
+	fn closest_material_on_a_two_material_quad() {
+		let client = test_client();
+		let mesh = create_field(&client, "createMeshField", "two_tone", Vec3::ZERO, |vec| {
+			let mut vertices = vec.start_vector();
+			for vertex in [Vec3::ZERO, Vec3::X, vec3(1_f32, 1_f32, 0_f32), Vec3::Y] {
+				for coordinate in vertex.to_array() {
+					vertices.push(coordinate);
+				}
+			}
+			vertices.end_vector();
+			let mut indices = vec.start_vector();
+			for index in [0_u32, 1, 2, 0, 2, 3] {
+				indices.push(index);
+			}
+			indices.end_vector();
+			let mut materials = vec.start_vector();
+			materials.push(7_u32);
+			materials.push(9_u32);
+			materials.end_vector();
+		});
+		let closest_material = |point: Vec3| {
+			let hit = method(
+				&client,
+				mesh.get_path(),
+				"closest_material",
+				&point_args(point),
+			)
+			.unwrap();
+			let hit = flexbuffers::Reader::get_root(hit.as_slice())
+				.unwrap()
+				.as_vector();
+			let closest_point: Vec3 = flex_to_vec3!(hit.idx(0)).unwrap().into();
+			(closest_point, hit.idx(1).as_u32())
+		};
+		let (point, material) = closest_material(vec3(0.75_f32, 0.25_f32, 0.3_f32));
+		assert_eq!(material, 7);
+		assert!(
+			point.abs_diff_eq(vec3(0.75_f32, 0.25_f32, 0_f32), 0.0001_f32),
+			"{}",
+			point
+		);
+		let (point, material) = closest_material(vec3(0.2_f32, 0.6_f32, -0.2_f32));
+		assert_eq!(material, 9);
+		assert!(
+			point.abs_diff_eq(vec3(0.2_f32, 0.6_f32, 0_f32), 0.0001_f32),
+			"{}",
+			point
+		);
+
+		let sphere = create_sphere(&client, "untextured", Vec3::ZERO, 1_f32);
+		assert!(method(
+			&client,
+			sphere.get_path(),
+			"closest_material",
+			&point_args(Vec3::ZERO)
+		)
+		.is_err());
+	}
+
+	#[test]
+	fn bounding_sphere_fits_scattered_points() {
+		let client = test_client();
+		let center = Vec3::ONE;
+		let mut state = 9_u64;
+		// the axis extremes on a radius 2 sphere make 2 the smallest radius, the rest are inside
+		let mut points: Vec<Vec3> = [Vec3::X, -Vec3::X, Vec3::Y, -Vec3::Y, Vec3::Z, -Vec3::Z]
+			.iter()
+			.map(|axis| center + (*axis * 2_f32))
+			.collect();
+		points.extend((0..50).map(|_| {
+			center
+				+ (Vec3::from(random_direction(&mut state)) * (next_random(&mut state) * 1.9_f32))
+		}));
+		signal(
+			&client,
+			"/field",
+			"createSphereFieldBounding",
+			&flexbuffer_from_vector_arguments(|vec| {
+				vec.push("enclosure");
+				vec.push("/");
+				let mut point_vec = vec.start_vector();
+				for point in &points {
+					push_to_vec!(&mut point_vec, mint::Vector3::from(*point));
+				}
+				point_vec.end_vector();
+			}),
+		);
+		let enclosure = client.scenegraph.get_node("/field/enclosure").unwrap();
+		for point in &points {
+			assert!(
+				distance_at(&client, &enclosure, *point) <= 0.0001_f32,
+				"{} is outside",
+				point
+			);
+		}
+		let fitted_center = enclosure
+			.spatial
+			.get()
+			.unwrap()
+			.global_transform()
+			.transform_point3(Vec3::ZERO);
+		let radius = -distance_at(&client, &enclosure, fitted_center);
+		assert!((2_f32..2.1_f32).contains(&radius), "radius {}", radius);
+	}
+}
@@ -28,6 +28,7 @@ impl Pointer {
 				origin: vec3(0_f32, 0_f32, 0_f32),
 				direction: vec3(0_f32, 0_f32, 1_f32),
 				space: space.clone(),
+				max_steps: None,
 			},
 			field,
 		)
@@ -1,4 +1,5 @@
-use super::core::Node;
+use super::core::{flex_args, Node};
+use super::field::DistanceCache;
 use crate::core::client::Client;
 use anyhow::{anyhow, ensure, Result};
 use glam::{Mat4, Quat, Vec3};
@@ -37,11 +38,47 @@ impl Spatial {
 	}
 
 	pub fn space_to_space_matrix(from: Option<&Spatial>, to: Option<&Spatial>) -> Mat4 {
+		// avoid round tripping through the world (and a lossy inverse) for the same space
+		if let (Some(from), Some(to)) = (from, to) {
+			if std::ptr::eq(from, to) {
+				return Mat4::IDENTITY;
+			}
+		}
 		let space_to_world_matrix = from.map_or(Mat4::IDENTITY, |from| from.global_transform());
 		let world_to_space_matrix = to.map_or(Mat4::IDENTITY, |to| to.global_transform().inverse());
 		world_to_space_matrix * space_to_world_matrix
 	}
 
+	fn ancestors(spatial: &Arc<Spatial>) -> Vec<Arc<Spatial>> {
+		let mut ancestors = vec![spatial.clone()];
+		let mut current = spatial.parent.lock().clone();
+		while let Some(ancestor) = current {
+			current = ancestor.parent.lock().clone();
+			ancestors.push(ancestor);
+		}
+		ancestors
+	}
+	/// The spatial `depth` levels up the parent chain, so 1 is the parent and 0 is `None`.
+	pub fn ancestor(&self, depth: usize) -> Option<Arc<Spatial>> {
+		if depth == 0 {
+			return None;
+		}
+		let mut current = self.parent.lock().clone();
+		for _ in 1..depth {
+			current = current?.parent.lock().clone();
+		}
+		current
+	}
+	/// The nearest spatial that both `a` and `b` are (or descend from), if they share a tree.
+	pub fn common_ancestor(a: &Arc<Spatial>, b: &Arc<Spatial>) -> Option<Arc<Spatial>> {
+		let b_ancestors = Spatial::ancestors(b);
+		Spatial::ancestors(a).into_iter().find(|a_ancestor| {
+			b_ancestors
+				.iter()
+				.any(|b_ancestor| Arc::ptr_eq(a_ancestor, b_ancestor))
+		})
+	}
+
 	pub fn local_transform(&self) -> Mat4 {
 		*self.transform.lock()
 	}
@@ -53,6 +90,8 @@ impl Spatial {
 	}
 	pub fn set_local_transform(&self, transform: Mat4) {
 		*self.transform.lock() = transform;
+		// every field in this spatial's subtree just moved
+		DistanceCache::invalidate_all();
 	}
 	pub fn set_local_transform_components(
 		&self,
@@ -156,10 +195,10 @@ pub fn get_spatial_parent_flex(
 	Ok(calling_client
 		.scenegraph
 		.get_node(node_path)
-		.ok_or_else(|| anyhow!("Spatial parent node not found"))?
+		.ok_or_else(|| anyhow!("Spatial parent node {} not found", node_path))?
 		.spatial
 		.get()
-		.ok_or_else(|| anyhow!("Spatial parent node is not a spatial"))?
+		.ok_or_else(|| anyhow!("Spatial parent node {} is not a spatial", node_path))?
 		.clone())
 }
 pub fn get_transform_pose_flex<B: flexbuffers::Buffer>(
@@ -179,6 +218,7 @@ pub fn get_transform_pose_flex<B: flexbuffers::Buffer>(
 pub fn create_interface(client: &Arc<Client>) {
 	let node = Node::create(client, "", "spatial", false);
 	node.add_local_signal("createSpatial", create_spatial_flex);
+	node.add_local_signal("createAnchor", create_anchor_flex);
 	node.add_to_scenegraph();
 }
 
@@ -206,3 +246,151 @@ pub fn create_spatial_flex(_node: &Node, calling_client: Arc<Client>, data: &[u8
 	Spatial::add_to(&node, Some(parent), transform)?;
 	Ok(())
 }
+
+/// A spatial with only a pose, kept apart from other spatials so clients can use it as a stable
+/// reference space for queries. It adds no scale of its own, so distances measured in it are
+/// only scaled as much as its parent is.
+pub fn create_anchor_flex(_node: &Node, calling_client: Arc<Client>, data: &[u8]) -> Result<()> {
+	let flex_vec = flex_args(data, &["name", "parent", "position", "rotation"])?;
+	let node = Node::create(
+		&calling_client,
+		"/spatial/anchor",
+		flex_vec.idx(0).get_str()?,
+		true,
+	);
+	let parent = get_spatial_parent_flex(&calling_client, flex_vec.idx(1).get_str()?)?;
+	let transform = get_transform_pose_flex(&flex_vec.idx(2), &flex_vec.idx(3))?;
+	let node = node.add_to_scenegraph();
+	Spatial::add_to(&node, Some(parent), transform)?;
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::nodes::{field, root::Root};
+
+	fn create_anchor(client: &Arc<Client>, name: &str, parent: &str, position: Vec3) -> Result<()> {
+		client
+			.scenegraph
+			.get_node("/spatial")
+			.unwrap()
+			.send_local_signal(
+				client.clone(),
+				"createAnchor",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push(name);
+					vec.push(parent);
+					push_to_vec!(
+						vec,
+						mint::Vector3::from(position),
+						mint::Quaternion::from(Quat::IDENTITY)
+					);
+				}),
+			)?;
+		Ok(())
+	}
+
+	#[test]
+	fn anchor_as_a_reference_space() {
+		let client = Client::new_local();
+		let _ = client.root.set(Root::create(&client));
+		create_interface(&client);
+		field::create_interface(&client);
+		client
+			.scenegraph
+			.get_node("/field")
+			.unwrap()
+			.send_local_signal(
+				client.clone(),
+				"createSphereField",
+				&flexbuffer_from_vector_arguments(|vec| {
+					vec.push("ball");
+					vec.push("/");
+					push_to_vec!(vec, mint::Vector3::from(Vec3::ZERO), 1_f32);
+				}),
+			)
+			.unwrap();
+		let distance_from = |reference_space: &str| {
+			let distance = client
+				.scenegraph
+				.get_node("/field/ball")
+				.unwrap()
+				.execute_local_method(
+					client.clone(),
+					"distance",
+					&flexbuffer_from_vector_arguments(|vec| {
+						vec.push(reference_space);
+						push_to_vec!(vec, mint::Vector3::from(Vec3::ZERO));
+					}),
+				)
+				.unwrap();
+			flexbuffers::Reader::get_root(distance.as_slice())
+				.unwrap()
+				.as_f32()
+		};
+
+		create_anchor(&client, "above", "/", Vec3::Y * 3_f32).unwrap();
+		assert!((distance_from("/spatial/anchor/above") - 2_f32).abs() < 0.0001_f32);
+
+		// under a scaled parent, distances are in the parent's (halved) units
+		let scaled = client.scenegraph.get_node("/spatial/anchor/above").unwrap();
+		scaled
+			.spatial
+			.get()
+			.unwrap()
+			.set_local_transform(Mat4::from_scale_rotation_translation(
+				Vec3::splat(2_f32),
+				Quat::IDENTITY,
+				Vec3::Y * 3_f32,
+			));
+		create_anchor(&client, "child", "/spatial/anchor/above", Vec3::Y * 0.5_f32).unwrap();
+		assert!((distance_from("/spatial/anchor/child") - 1.5_f32).abs() < 0.0001_f32);
+
+		assert!(create_anchor(&client, "orphan", "/nowhere", Vec3::ZERO).is_err());
+		assert!(client
+			.scenegraph
+			.get_node("/spatial/anchor/orphan")
+			.is_none());
+	}
+
+	#[test]
+	fn common_ancestor_in_a_tree() {
+		let client = Client::new_local();
+		let spatial = |name: &str, parent: Option<&Arc<Spatial>>| {
+			let node = Node::create(&client, "", name, false).add_to_scenegraph();
+			Spatial::add_to(&node, parent.cloned(), Mat4::IDENTITY).unwrap()
+		};
+		//      root       other
+		//     /    \
+		//    a      b
+		//    |
+		//    c
+		//    |
+		//    d
+		let root = spatial("root", None);
+		let a = spatial("a", Some(&root));
+		let b = spatial("b", Some(&root));
+		let c = spatial("c", Some(&a));
+		let d = spatial("d", Some(&c));
+		let other = spatial("other", None);
+
+		let common = |x: &Arc<Spatial>, y: &Arc<Spatial>| Spatial::common_ancestor(x, y);
+		for (x, y, expected) in [
+			(&c, &b, &root),
+			(&d, &b, &root),
+			(&d, &a, &a),
+			(&a, &d, &a),
+			(&d, &c, &c),
+			(&c, &c, &c),
+		] {
+			assert!(Arc::ptr_eq(&common(x, y).unwrap(), expected));
+		}
+		assert!(common(&d, &other).is_none());
+
+		assert!(Arc::ptr_eq(&d.ancestor(1).unwrap(), &c));
+		assert!(Arc::ptr_eq(&d.ancestor(3).unwrap(), &root));
+		assert!(d.ancestor(4).is_none());
+		assert!(d.ancestor(0).is_none());
+	}
+}